@@ -1,5 +1,5 @@
 use fs::read_dir;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::env::var_os;
 use std::fs;
 use std::path::Path;
@@ -7,7 +7,7 @@ use std::path::Path;
 fn main() -> std::io::Result<()> {
     let files = read_dir("src").unwrap();
 
-    let mut solvers: HashMap<u16, Vec<u8>> = HashMap::new();
+    let mut solvers: BTreeMap<u16, Vec<u8>> = BTreeMap::new();
     for f in files {
         let file = f.unwrap();
         let file_name = file.file_name().into_string().unwrap();
@@ -26,38 +26,20 @@ fn main() -> std::io::Result<()> {
         }
     }
 
-    let mut matches: Vec<String> = Vec::new();
-
-    for y in &solvers {
-        let year = y.0;
-        y.1.iter().for_each(|d| {
-            matches.push(format!(
-                "    (Some({year}), Some({d})) => {},",
-                format_solver_builder(year, d)
-            ))
-        });
-        matches.push(format!(
-            "    (Some({year}), None) => {},",
-            format_solver_builder(year, y.1.iter().max().unwrap())
-        ));
-        matches.push(format!(
-            "    (Some({year}), Some(d)) => Err(AdventError::UnknownDay({year}, *d)),"
-        ));
+    let mut entries: Vec<String> = Vec::new();
+    for (year, days) in &solvers {
+        for day in days {
+            entries.push(format_entry(year, day));
+        }
     }
 
-    let years = &solvers.into_keys().collect::<Vec<_>>();
-    let last_year = years.iter().max().unwrap();
-
-    matches.push(format!(
-        "    (None, d) => solver_builder(&Some({last_year}), d),"
-    ));
-    matches.push("    (Some(y), _) => Err(AdventError::UnknownYear(*y)),".to_string());
-
-    let path = Path::new(&var_os("OUT_DIR").unwrap()).join("matches.txt");
-    let text = format!("  match (year, day) {{\n{}\n  }}", matches.join("\n"));
+    let path = Path::new(&var_os("OUT_DIR").unwrap()).join("registry.txt");
+    let text = format!("&[\n{}\n]", entries.join("\n"));
     fs::write(path, text)
 }
 
-fn format_solver_builder(year: &u16, day: &u8) -> String {
-    format!("Ok((|input: &str| Box::new(crate::year{year}::day{day:02}::Advent{year}Day{day:02}Solver::new(input)), \"{year}\".to_string(), \"{day:02}\".to_string()))")
+fn format_entry(year: &u16, day: &u8) -> String {
+    format!(
+        "    SolverEntry {{ year: {year}, day: {day}, name: \"Advent{year}Day{day:02}Solver\", builder: |input: &str| Box::new(crate::year{year}::day{day:02}::Advent{year}Day{day:02}Solver::new(input)) }},"
+    )
 }
@@ -0,0 +1,65 @@
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Shared flag asking a solver to stop. Solvers do not receive it directly, the thread running
+/// them installs it and long loops poll [`cancelled`].
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Makes this token the one polled by [`cancelled`] on the current thread.
+    pub fn install(&self) {
+        CURRENT.with(|current| *current.borrow_mut() = Some(self.clone()));
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+/// Whether the solver running on this thread should give up. Loops that can run for a long
+/// time poll it and bail out early, whatever they return then is thrown away.
+pub fn cancelled() -> bool {
+    CURRENT.with(|current| current.borrow().as_ref().is_some_and(|t| t.is_cancelled()))
+}
+
+/// Token installed on the current thread, to hand over to threads a solver spawns itself.
+pub fn current() -> Option<CancellationToken> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn polls_the_token_of_the_current_thread() {
+        assert!(!cancelled());
+        let token = CancellationToken::new();
+        let worker = token.clone();
+        let handle = std::thread::spawn(move || {
+            worker.install();
+            while !cancelled() {
+                std::thread::yield_now();
+            }
+        });
+        token.cancel();
+        handle.join().unwrap();
+        assert!(!cancelled());
+    }
+}
@@ -0,0 +1,363 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Debug, Formatter, Write};
+
+use itertools::Itertools;
+
+/// Boolean or fixed-width word circuit where every wire is driven by exactly one gate.
+///
+/// Parses both `x AND y -> z` gate lines, with `NOT`, `OR`, `XOR`, `LSHIFT` and `RSHIFT`
+/// operators and literal operands, and `x00: 1` input lines.
+#[derive(Clone)]
+pub struct Circuit {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    gates: Vec<Gate>,
+    mask: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operand {
+    Wire(usize),
+    Constant(u64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    Buffer,
+    Not,
+    And,
+    Or,
+    Xor,
+    LShift,
+    RShift,
+}
+
+#[derive(Clone, Debug)]
+pub struct Gate {
+    pub operation: Operation,
+    pub inputs: Vec<Operand>,
+    pub output: usize,
+}
+
+#[derive(PartialEq, Eq)]
+pub enum CircuitError {
+    Parse(String),
+    MultipleDrivers(String),
+    Undriven(Vec<String>),
+    Cycle(Vec<String>),
+}
+
+impl Circuit {
+    /// Words are truncated to `width` bits after every gate.
+    pub fn parse(input: &str, width: u32) -> Result<Self, CircuitError> {
+        let mut circuit = Self {
+            names: Vec::new(),
+            ids: HashMap::new(),
+            gates: Vec::new(),
+            mask: if width >= 64 {
+                u64::MAX
+            } else {
+                (1 << width) - 1
+            },
+        };
+        for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let (expression, output) = if let Some((wire, value)) = line.split_once(": ") {
+                (value, wire)
+            } else if let Some(split) = line.split_once(" -> ") {
+                split
+            } else {
+                return Err(CircuitError::Parse(line.to_string()));
+            };
+            let tokens = expression.split(' ').collect_vec();
+            let (operation, operands) = match tokens[..] {
+                [a] => (Operation::Buffer, vec![a]),
+                ["NOT", a] => (Operation::Not, vec![a]),
+                [a, op, b] => (
+                    match op {
+                        "AND" => Operation::And,
+                        "OR" => Operation::Or,
+                        "XOR" => Operation::Xor,
+                        "LSHIFT" => Operation::LShift,
+                        "RSHIFT" => Operation::RShift,
+                        _ => return Err(CircuitError::Parse(line.to_string())),
+                    },
+                    vec![a, b],
+                ),
+                _ => return Err(CircuitError::Parse(line.to_string())),
+            };
+            let inputs = operands.into_iter().map(|o| circuit.operand(o)).collect();
+            let output = circuit.wire(output);
+            if circuit.driver(output).is_some() {
+                return Err(CircuitError::MultipleDrivers(circuit.names[output].clone()));
+            }
+            circuit.gates.push(Gate {
+                operation,
+                inputs,
+                output,
+            });
+        }
+        Ok(circuit)
+    }
+
+    fn wire(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), self.names.len() - 1);
+        self.names.len() - 1
+    }
+
+    fn operand(&mut self, token: &str) -> Operand {
+        match token.parse() {
+            Ok(value) => Operand::Constant(value),
+            Err(_) => Operand::Wire(self.wire(token)),
+        }
+    }
+
+    fn driver(&self, wire: usize) -> Option<&Gate> {
+        self.gates.iter().find(|g| g.output == wire)
+    }
+
+    pub fn gates(&self) -> &[Gate] {
+        &self.gates
+    }
+
+    pub fn name(&self, wire: usize) -> &str {
+        &self.names[wire]
+    }
+
+    pub fn wires(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Replaces whatever drives `wire` with a constant value.
+    pub fn set(&mut self, wire: &str, value: u64) {
+        let output = self.wire(wire);
+        self.gates.retain(|g| g.output != output);
+        self.gates.push(Gate {
+            operation: Operation::Buffer,
+            inputs: vec![Operand::Constant(value)],
+            output,
+        });
+    }
+
+    /// Orders gates so each one comes after the gates driving its inputs.
+    pub fn topological_order(&self) -> Result<Vec<usize>, CircuitError> {
+        let mut driven = vec![false; self.names.len()];
+        self.gates.iter().for_each(|g| driven[g.output] = true);
+        let undriven = self
+            .gates
+            .iter()
+            .flat_map(|g| &g.inputs)
+            .filter_map(|&o| match o {
+                Operand::Wire(w) if !driven[w] => Some(self.names[w].clone()),
+                _ => None,
+            })
+            .sorted()
+            .dedup()
+            .collect_vec();
+        if !undriven.is_empty() {
+            return Err(CircuitError::Undriven(undriven));
+        }
+
+        let mut readers: Vec<Vec<usize>> = vec![Vec::new(); self.names.len()];
+        let mut pending: Vec<usize> = vec![0; self.gates.len()];
+        for (i, gate) in self.gates.iter().enumerate() {
+            for &input in &gate.inputs {
+                if let Operand::Wire(w) = input {
+                    readers[w].push(i);
+                    pending[i] += 1;
+                }
+            }
+        }
+        let mut queue: VecDeque<usize> =
+            (0..self.gates.len()).filter(|&i| pending[i] == 0).collect();
+        let mut order = Vec::with_capacity(self.gates.len());
+        while let Some(i) = queue.pop_front() {
+            order.push(i);
+            for &reader in &readers[self.gates[i].output] {
+                pending[reader] -= 1;
+                if pending[reader] == 0 {
+                    queue.push_back(reader);
+                }
+            }
+        }
+        if order.len() < self.gates.len() {
+            return Err(CircuitError::Cycle(
+                (0..self.gates.len())
+                    .filter(|&i| pending[i] > 0)
+                    .map(|i| self.names[self.gates[i].output].clone())
+                    .sorted()
+                    .collect(),
+            ));
+        }
+        Ok(order)
+    }
+
+    pub fn evaluate(&self) -> Result<HashMap<String, u64>, CircuitError> {
+        let mut values = vec![0u64; self.names.len()];
+        for i in self.topological_order()? {
+            let gate = &self.gates[i];
+            let input = |n: usize| match gate.inputs[n] {
+                Operand::Wire(w) => values[w],
+                Operand::Constant(c) => c,
+            };
+            let value = match gate.operation {
+                Operation::Buffer => input(0),
+                Operation::Not => !input(0),
+                Operation::And => input(0) & input(1),
+                Operation::Or => input(0) | input(1),
+                Operation::Xor => input(0) ^ input(1),
+                Operation::LShift => input(0) << input(1),
+                Operation::RShift => input(0) >> input(1),
+            };
+            values[gate.output] = value & self.mask;
+        }
+        Ok(self.names.iter().cloned().zip(values).collect())
+    }
+
+    /// Reads the wires named `{prefix}00`, `{prefix}01`, ... as the bits of a little-endian number.
+    pub fn number(values: &HashMap<String, u64>, prefix: char) -> u64 {
+        values
+            .iter()
+            .filter(|(name, _)| name.starts_with(prefix))
+            .filter_map(|(name, &v)| Some((name[1..].parse::<u32>().ok()?, v)))
+            .fold(0, |acc, (bit, v)| acc | ((v & 1) << bit))
+    }
+
+    /// Lists the outputs of gates breaking the ripple-carry adder structure adding the `x` and
+    /// `y` wires into the `z` wires:
+    /// `z = x ^ y ^ carry` and `carry' = (x & y) | ((x ^ y) & carry)` with a half adder for bit 0.
+    pub fn miswired_adder_wires(&self) -> Vec<String> {
+        let is_input =
+            |o: &Operand| matches!(o, Operand::Wire(w) if self.names[*w].starts_with(['x', 'y']));
+        let is_first_bit = |g: &Gate| {
+            g.inputs
+                .iter()
+                .any(|o| matches!(o, Operand::Wire(w) if self.names[*w] == "x00"))
+        };
+        let feeds = |wire: usize, operation: Operation| {
+            self.gates
+                .iter()
+                .any(|g| g.operation == operation && g.inputs.contains(&Operand::Wire(wire)))
+        };
+        let last_z = self
+            .names
+            .iter()
+            .filter(|n| n.starts_with('z'))
+            .max()
+            .cloned()
+            .unwrap_or_default();
+        self.gates
+            .iter()
+            .filter(|g| {
+                let name = &self.names[g.output];
+                let from_inputs = g.inputs.iter().all(is_input);
+                match g.operation {
+                    _ if *name == last_z => g.operation != Operation::Or,
+                    Operation::Xor if name.starts_with('z') => false,
+                    _ if name.starts_with('z') => true,
+                    Operation::Xor if !from_inputs => true,
+                    Operation::Xor => !is_first_bit(g) && !feeds(g.output, Operation::Xor),
+                    Operation::And => !is_first_bit(g) && !feeds(g.output, Operation::Or),
+                    _ => false,
+                }
+            })
+            .map(|g| self.names[g.output].clone())
+            .sorted()
+            .collect()
+    }
+
+    /// Graphviz representation with one node per wire and one per gate.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph circuit {\n  rankdir=LR;\n");
+        for name in &self.names {
+            let _ = writeln!(dot, "  \"{name}\" [shape=ellipse];");
+        }
+        for (i, gate) in self.gates.iter().enumerate() {
+            let _ = writeln!(dot, "  g{i} [shape=box, label=\"{:?}\"];", gate.operation);
+            for input in &gate.inputs {
+                match input {
+                    Operand::Wire(w) => {
+                        let _ = writeln!(dot, "  \"{}\" -> g{i};", self.names[*w]);
+                    }
+                    Operand::Constant(c) => {
+                        let _ = writeln!(dot, "  g{i}c{c} [shape=plaintext, label=\"{c}\"];");
+                        let _ = writeln!(dot, "  g{i}c{c} -> g{i};");
+                    }
+                }
+            }
+            let _ = writeln!(dot, "  g{i} -> \"{}\";", self.names[gate.output]);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl Debug for CircuitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitError::Parse(line) => f.write_fmt(format_args!("Invalid circuit line {line}")),
+            CircuitError::MultipleDrivers(wire) => {
+                f.write_fmt(format_args!("Wire {wire} is driven by multiple gates"))
+            }
+            CircuitError::Undriven(wires) => {
+                f.write_fmt(format_args!("Wires {} are never driven", wires.join(",")))
+            }
+            CircuitError::Cycle(wires) => {
+                f.write_fmt(format_args!("Wires {} form a cycle", wires.join(",")))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn detects_undriven_wires_and_cycles() {
+        let circuit = Circuit::parse("a AND b -> c\n1 -> a", 16).unwrap();
+        assert_eq!(
+            circuit.evaluate().unwrap_err(),
+            CircuitError::Undriven(vec!["b".to_string()])
+        );
+        let circuit = Circuit::parse("a OR b -> c\nc -> a\n1 -> b", 16).unwrap();
+        assert_eq!(
+            circuit.evaluate().unwrap_err(),
+            CircuitError::Cycle(vec!["a".to_string(), "c".to_string()])
+        );
+    }
+
+    #[test]
+    fn finds_miswired_adder_gates() {
+        // two bit adder with the second bit's sum and carry gates swapped
+        let circuit = Circuit::parse(
+            "\
+x00: 1
+x01: 1
+y00: 1
+y01: 0
+x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> a01
+x01 AND y01 -> s01
+s01 XOR c00 -> z01
+s01 AND c00 -> b01
+a01 OR b01 -> z02
+",
+            1,
+        )
+        .unwrap();
+        assert_eq!(circuit.miswired_adder_wires(), vec!["a01", "s01"]);
+    }
+
+    #[test]
+    fn exports_dot() {
+        let circuit = Circuit::parse("x AND 1 -> y", 16).unwrap();
+        let dot = circuit.to_dot();
+        assert!(dot.contains("\"x\" -> g0;"));
+        assert!(dot.contains("g0 -> \"y\";"));
+    }
+}
@@ -0,0 +1,246 @@
+use std::fmt::{Debug, Formatter};
+
+/// Axis-aligned box of integer points, bounds are inclusive on every axis.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cuboid<const N: usize> {
+    pub min: [i64; N],
+    pub max: [i64; N],
+}
+
+impl<const N: usize> Cuboid<N> {
+    pub fn new(min: [i64; N], max: [i64; N]) -> Self {
+        Self { min, max }
+    }
+
+    pub fn point(p: [i64; N]) -> Self {
+        Self { min: p, max: p }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..N).any(|i| self.min[i] > self.max[i])
+    }
+
+    pub fn side(&self, axis: usize) -> u64 {
+        if self.min[axis] > self.max[axis] {
+            0
+        } else {
+            self.max[axis].abs_diff(self.min[axis]) + 1
+        }
+    }
+
+    pub fn volume(&self) -> u128 {
+        (0..N).map(|i| self.side(i) as u128).product()
+    }
+
+    pub fn contains(&self, p: &[i64; N]) -> bool {
+        (0..N).all(|i| self.min[i] <= p[i] && p[i] <= self.max[i])
+    }
+
+    pub fn contains_cuboid(&self, other: &Self) -> bool {
+        (0..N).all(|i| self.min[i] <= other.min[i] && other.max[i] <= self.max[i])
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        (0..N).all(|i| self.min[i] <= other.max[i] && other.min[i] <= self.max[i])
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let c = Self {
+            min: std::array::from_fn(|i| self.min[i].max(other.min[i])),
+            max: std::array::from_fn(|i| self.max[i].min(other.max[i])),
+        };
+        (!c.is_empty()).then_some(c)
+    }
+
+    /// Splits the points of `self` not in `other` into at most `2 * N` disjoint cuboids.
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let Some(inner) = self.intersection(other) else {
+            return vec![*self];
+        };
+        let mut pieces = Vec::new();
+        let mut rest = *self;
+        for axis in 0..N {
+            if rest.min[axis] < inner.min[axis] {
+                let mut below = rest;
+                below.max[axis] = inner.min[axis] - 1;
+                pieces.push(below);
+            }
+            if rest.max[axis] > inner.max[axis] {
+                let mut above = rest;
+                above.min[axis] = inner.max[axis] + 1;
+                pieces.push(above);
+            }
+            rest.min[axis] = inner.min[axis];
+            rest.max[axis] = inner.max[axis];
+        }
+        pieces
+    }
+
+    pub fn translate(&self, offset: [i64; N]) -> Self {
+        Self {
+            min: std::array::from_fn(|i| self.min[i] + offset[i]),
+            max: std::array::from_fn(|i| self.max[i] + offset[i]),
+        }
+    }
+
+    /// Stretches the cuboid infinitely along an axis, useful to compare projections on the others.
+    pub fn extended(&self, axis: usize) -> Self {
+        let mut c = *self;
+        c.min[axis] = i64::MIN;
+        c.max[axis] = i64::MAX;
+        c
+    }
+
+    pub fn manhattan_distance(&self, p: &[i64; N]) -> u64 {
+        (0..N)
+            .map(|i| {
+                if p[i] < self.min[i] {
+                    self.min[i].abs_diff(p[i])
+                } else if p[i] > self.max[i] {
+                    p[i].abs_diff(self.max[i])
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+
+    /// Halves the cuboid along every axis longer than one, yielding up to `2^N` children.
+    pub fn split(&self) -> Vec<Self> {
+        let mut children = vec![*self];
+        for axis in 0..N {
+            if self.side(axis) < 2 {
+                continue;
+            }
+            let mid = self.min[axis] + (self.max[axis] - self.min[axis]) / 2;
+            children = children
+                .into_iter()
+                .flat_map(|c| {
+                    let mut low = c;
+                    let mut high = c;
+                    low.max[axis] = mid;
+                    high.min[axis] = mid + 1;
+                    [low, high]
+                })
+                .collect();
+        }
+        children
+    }
+}
+
+/// Rotates 3D points into the 4 diagonal coordinates in which Manhattan balls become boxes.
+pub fn rotate_manhattan(p: [i64; 3]) -> [i64; 4] {
+    [
+        p[0] + p[1] + p[2],
+        p[0] + p[1] - p[2],
+        p[0] - p[1] + p[2],
+        -p[0] + p[1] + p[2],
+    ]
+}
+
+impl Cuboid<4> {
+    /// Points within `radius` of `center` are exactly those whose rotation lies in this box.
+    pub fn from_manhattan_ball(center: [i64; 3], radius: i64) -> Self {
+        let c = rotate_manhattan(center);
+        Self {
+            min: c.map(|v| v - radius),
+            max: c.map(|v| v + radius),
+        }
+    }
+}
+
+impl<const N: usize> Debug for Cuboid<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let axes = (0..N)
+            .map(|i| format!("{}..={}", self.min[i], self.max[i]))
+            .collect::<Vec<_>>();
+        f.write_fmt(format_args!("Cuboid({})", axes.join(", ")))
+    }
+}
+
+/// Union of cuboids kept as disjoint pieces so volumes can be summed directly.
+#[derive(Clone, Debug, Default)]
+pub struct CuboidSet<const N: usize> {
+    pieces: Vec<Cuboid<N>>,
+}
+
+impl<const N: usize> CuboidSet<N> {
+    pub fn new() -> Self {
+        Self { pieces: Vec::new() }
+    }
+
+    pub fn insert(&mut self, cuboid: Cuboid<N>) {
+        if self.pieces.iter().any(|p| p.contains_cuboid(&cuboid)) {
+            return;
+        }
+        self.remove(&cuboid);
+        self.pieces.push(cuboid);
+    }
+
+    pub fn remove(&mut self, cuboid: &Cuboid<N>) {
+        let mut pieces = Vec::with_capacity(self.pieces.len());
+        for p in self.pieces.drain(..) {
+            if p.intersects(cuboid) {
+                pieces.extend(p.subtract(cuboid));
+            } else {
+                pieces.push(p);
+            }
+        }
+        self.pieces = pieces;
+    }
+
+    pub fn contains(&self, p: &[i64; N]) -> bool {
+        self.pieces.iter().any(|c| c.contains(p))
+    }
+
+    pub fn pieces(&self) -> &[Cuboid<N>] {
+        &self.pieces
+    }
+
+    pub fn volume(&self) -> u128 {
+        self.pieces.iter().map(Cuboid::volume).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn subtracts_into_disjoint_pieces() {
+        let outer = Cuboid::new([0, 0, 0], [9, 9, 9]);
+        let inner = Cuboid::new([3, 3, 3], [5, 5, 5]);
+        let pieces = outer.subtract(&inner);
+        assert_eq!(pieces.len(), 6);
+        assert_eq!(pieces.iter().map(Cuboid::volume).sum::<u128>(), 1000 - 27);
+        for i in 0..pieces.len() {
+            assert!(!pieces[i].intersects(&inner));
+            for j in i + 1..pieces.len() {
+                assert!(!pieces[i].intersects(&pieces[j]));
+            }
+        }
+    }
+
+    #[test]
+    fn computes_union_volume() {
+        let mut set = CuboidSet::new();
+        set.insert(Cuboid::new([10, 10, 10], [12, 12, 12]));
+        set.insert(Cuboid::new([11, 11, 11], [13, 13, 13]));
+        set.remove(&Cuboid::new([9, 9, 9], [11, 11, 11]));
+        set.insert(Cuboid::new([10, 10, 10], [10, 10, 10]));
+        assert_eq!(set.volume(), 39);
+    }
+
+    #[test]
+    fn converts_manhattan_balls() {
+        let ball = Cuboid::from_manhattan_ball([1, 2, 3], 4);
+        for x in -5..8 {
+            for y in -5..8 {
+                for z in -5..8 {
+                    let inside = (x - 1i64).abs() + (y - 2i64).abs() + (z - 3i64).abs() <= 4;
+                    assert_eq!(ball.contains(&rotate_manhattan([x, y, z])), inside);
+                }
+            }
+        }
+    }
+}
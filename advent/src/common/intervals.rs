@@ -0,0 +1,274 @@
+use std::fmt::{Debug, Formatter};
+use std::ops::RangeInclusive;
+
+use num_traits::PrimInt;
+
+/// Set of integers stored as sorted, disjoint and non-adjacent inclusive ranges.
+#[derive(Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    pub fn full() -> Self {
+        Self::from(T::min_value()..=T::max_value())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of integers in the set.
+    ///
+    /// Panics when it does not fit in a u128, which only happens when the set holds all 2^128
+    /// values of `i128` or `u128`.
+    pub fn len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .try_fold(0u128, |len, &(s, e)| span(s, e).and_then(|n| len.checked_add(n)))
+            .expect("interval set holds 2^128 values")
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.0)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|r| r.1)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let i = self.ranges.partition_point(|r| r.1 < *value);
+        i < self.ranges.len() && self.ranges[i].0 <= *value
+    }
+
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(s, e)| s..=e)
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        *self = self.union(&Self::from(range));
+    }
+
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        *self = self.difference(&Self::from(range));
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ranges = Vec::with_capacity(self.ranges.len() + other.ranges.len());
+        ranges.extend_from_slice(&self.ranges);
+        ranges.extend_from_slice(&other.ranges);
+        Self::normalized(ranges)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (ls, le) = self.ranges[i];
+            let (rs, re) = other.ranges[j];
+            let start = ls.max(rs);
+            let end = le.min(re);
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if le < re {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+        for &(start, end) in &self.ranges {
+            let mut start = Some(start);
+            while j < other.ranges.len() && other.ranges[j].1 < start.unwrap() {
+                j += 1;
+            }
+            let mut k = j;
+            while let Some(s) = start {
+                if k >= other.ranges.len() || other.ranges[k].0 > end {
+                    ranges.push((s, end));
+                    break;
+                }
+                let (os, oe) = other.ranges[k];
+                if os > s {
+                    ranges.push((s, os - T::one()));
+                }
+                start = if oe >= end { None } else { oe.checked_add(&T::one()) };
+                k += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn complement(&self) -> Self {
+        Self::full().difference(self)
+    }
+
+    fn normalized(mut ranges: Vec<(T, T)>) -> Self {
+        ranges.retain(|r| r.0 <= r.1);
+        ranges.sort_unstable();
+        let mut merged: Vec<(T, T)> = Vec::with_capacity(ranges.len());
+        for (s, e) in ranges {
+            match merged.last_mut() {
+                Some(last) if last.1 == T::max_value() || s <= last.1 + T::one() => {
+                    last.1 = last.1.max(e);
+                }
+                _ => merged.push((s, e)),
+            }
+        }
+        Self { ranges: merged }
+    }
+}
+
+/// Number of integers in `start..=end`, none for the full 128-bit range.
+fn span<T: PrimInt>(start: T, end: T) -> Option<u128> {
+    let distance = match (start.to_i128(), end.to_i128()) {
+        // end >= start, so the difference always fits in a u128 even when it wraps an i128
+        (Some(s), Some(e)) => e.wrapping_sub(s) as u128,
+        _ => (end - start).to_u128().unwrap(),
+    };
+    distance.checked_add(1)
+}
+
+impl<T: PrimInt> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PrimInt> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        Self::normalized(vec![range.into_inner()])
+    }
+}
+
+impl<T: PrimInt> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        Self::normalized(iter.into_iter().map(|r| r.into_inner()).collect())
+    }
+}
+
+impl<T: PrimInt + Debug> Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.ranges()).finish()
+    }
+}
+
+/// Piecewise translation of integers, mapping each source range onto a range of equal length
+/// starting at its destination. Values outside every source range are left unchanged.
+#[derive(Clone, Debug)]
+pub struct IntervalMap<T> {
+    pieces: Vec<(T, T, T)>,
+}
+
+impl<T: PrimInt> IntervalMap<T> {
+    pub fn new() -> Self {
+        Self { pieces: Vec::new() }
+    }
+
+    /// Adds a piece, ranges overlapping a previously inserted piece are shadowed by it.
+    pub fn insert(&mut self, source: RangeInclusive<T>, destination: T) {
+        let (start, end) = source.into_inner();
+        self.pieces.push((start, end, destination));
+    }
+
+    pub fn map(&self, value: T) -> T {
+        self.pieces
+            .iter()
+            .find(|&&(s, e, _)| s <= value && value <= e)
+            .map(|&(s, _, d)| d + (value - s))
+            .unwrap_or(value)
+    }
+
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut remaining = set.clone();
+        let mut mapped = Vec::new();
+        for &(start, end, destination) in &self.pieces {
+            let source = IntervalSet::from(start..=end);
+            for (s, e) in remaining.intersection(&source).ranges {
+                mapped.push((destination + (s - start), destination + (e - start)));
+            }
+            remaining = remaining.difference(&source);
+        }
+        mapped.extend(remaining.ranges);
+        IntervalSet::normalized(mapped)
+    }
+}
+
+impl<T: PrimInt> Default for IntervalMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn merges_overlapping_and_adjacent_ranges() {
+        let set: IntervalSet<u32> = [3..=5, 10..=14, 16..=20, 12..=18, 6..=6].into_iter().collect();
+        assert_eq!(set.ranges().collect::<Vec<_>>(), vec![3..=6, 10..=20]);
+        assert_eq!(set.len(), 15);
+        assert!(set.contains(&6));
+        assert!(!set.contains(&7));
+    }
+
+    #[test]
+    fn combines_sets() {
+        let left: IntervalSet<i32> = [-5..=5, 10..=20].into_iter().collect();
+        let right: IntervalSet<i32> = [0..=12, 18..=30].into_iter().collect();
+        assert_eq!(
+            left.union(&right).ranges().collect::<Vec<_>>(),
+            vec![-5..=30]
+        );
+        assert_eq!(
+            left.intersection(&right).ranges().collect::<Vec<_>>(),
+            vec![0..=5, 10..=12, 18..=20]
+        );
+        assert_eq!(
+            left.difference(&right).ranges().collect::<Vec<_>>(),
+            vec![-5..=-1, 13..=17]
+        );
+    }
+
+    #[test]
+    fn handles_type_bounds() {
+        let set: IntervalSet<u8> = [0..=10, 200..=255].into_iter().collect();
+        assert_eq!(set.complement().ranges().collect::<Vec<_>>(), vec![11..=199]);
+        assert_eq!(IntervalSet::<u8>::full().len(), 256);
+        assert!(IntervalSet::<u8>::full().difference(&IntervalSet::full()).is_empty());
+        assert_eq!(IntervalSet::from(-1..=i128::MAX).len(), (1 << 127) + 1);
+        assert_eq!(IntervalSet::from(0..=u128::MAX - 1).len(), u128::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "2^128")]
+    fn refuses_to_count_a_full_128_bit_range() {
+        IntervalSet::<i128>::full().len();
+    }
+
+    #[test]
+    fn maps_whole_sets() {
+        let mut map = IntervalMap::new();
+        map.insert(98..=99, 50);
+        map.insert(50..=97, 52);
+        assert_eq!(map.map(79), 81);
+        assert_eq!(map.map(14), 14);
+        let set: IntervalSet<u64> = [79..=92, 55..=67].into_iter().collect();
+        assert_eq!(
+            map.map_set(&set).ranges().collect::<Vec<_>>(),
+            vec![57..=69, 81..=94]
+        );
+    }
+}
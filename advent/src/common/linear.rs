@@ -0,0 +1,372 @@
+use num_integer::Integer;
+use num_traits::Signed;
+
+use crate::common::rational::Rational;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LinearSolution<T> {
+    Inconsistent,
+    Unique(Vec<Rational<T>>),
+    /// Solution with every free variable set to zero, along with the free variables' indices.
+    Underdetermined {
+        particular: Vec<Rational<T>>,
+        free: Vec<usize>,
+    },
+}
+
+/// Solves `a * x = b` by Gauss-Jordan elimination over exact rationals.
+pub fn solve_linear_system<T: Clone + Integer + Signed>(
+    a: &[Vec<Rational<T>>],
+    b: &[Rational<T>],
+) -> LinearSolution<T> {
+    let columns = a.first().map_or(0, |r| r.len());
+    let mut rows: Vec<Vec<Rational<T>>> = a
+        .iter()
+        .zip(b)
+        .map(|(r, v)| {
+            let mut row = r.clone();
+            row.push(v.clone());
+            row
+        })
+        .collect();
+    let mut pivots = Vec::new();
+    for column in 0..columns {
+        let rank = pivots.len();
+        let Some(pivot) = (rank..rows.len()).find(|&r| !rows[r][column].is_zero()) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+        let factor = rows[rank][column].clone();
+        rows[rank] = rows[rank]
+            .iter()
+            .map(|v| v.clone() / factor.clone())
+            .collect();
+        for r in 0..rows.len() {
+            if r == rank || rows[r][column].is_zero() {
+                continue;
+            }
+            let multiple = rows[r][column].clone();
+            for c in column..=columns {
+                let delta = multiple.clone() * rows[rank][c].clone();
+                rows[r][c] = rows[r][c].clone() - delta;
+            }
+        }
+        pivots.push(column);
+    }
+    if rows[pivots.len()..].iter().any(|r| !r[columns].is_zero()) {
+        return LinearSolution::Inconsistent;
+    }
+    let mut particular = vec![Rational::zero(); columns];
+    for (r, &c) in pivots.iter().enumerate() {
+        particular[c] = rows[r][columns].clone();
+    }
+    if pivots.len() == columns {
+        LinearSolution::Unique(particular)
+    } else {
+        LinearSolution::Underdetermined {
+            particular,
+            free: (0..columns).filter(|c| !pivots.contains(c)).collect(),
+        }
+    }
+}
+
+/// Returns the solution as integers if every component is whole.
+pub fn integer_solution<T: Clone + Integer + Signed>(x: &[Rational<T>]) -> Option<Vec<T>> {
+    x.iter().map(Rational::to_integer).collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProgramError {
+    Infeasible,
+    Unbounded,
+}
+
+/// Minimizes `c * x` subject to `a * x = b` and `x >= 0` with a two-phase simplex using Bland's rule.
+pub fn simplex<T: Clone + Integer + Signed>(
+    a: &[Vec<Rational<T>>],
+    b: &[Rational<T>],
+    c: &[Rational<T>],
+) -> Result<(Rational<T>, Vec<Rational<T>>), ProgramError> {
+    let rows = a.len();
+    let columns = c.len();
+    let width = columns + rows + 1;
+    let mut tableau: Vec<Vec<Rational<T>>> = (0..rows)
+        .map(|r| {
+            let flip = b[r].is_negative();
+            let sign = |v: &Rational<T>| if flip { -v.clone() } else { v.clone() };
+            let mut row: Vec<Rational<T>> = a[r].iter().map(sign).collect();
+            row.extend((0..rows).map(|i| {
+                if i == r {
+                    Rational::one()
+                } else {
+                    Rational::zero()
+                }
+            }));
+            row.push(sign(&b[r]));
+            row
+        })
+        .collect();
+    let mut basis: Vec<usize> = (columns..columns + rows).collect();
+
+    let mut phase1 = vec![Rational::zero(); width];
+    for row in &tableau {
+        for j in (0..columns).chain([width - 1]) {
+            phase1[j] = phase1[j].clone() - row[j].clone();
+        }
+    }
+    tableau.push(phase1);
+    pivot_until_optimal(&mut tableau, &mut basis, columns + rows)?;
+    if tableau[rows][width - 1].is_negative() {
+        return Err(ProgramError::Infeasible);
+    }
+    for r in 0..rows {
+        if basis[r] >= columns
+            && let Some(j) = (0..columns).find(|&j| !tableau[r][j].is_zero())
+        {
+            pivot(&mut tableau, &mut basis, r, j);
+        }
+    }
+
+    let mut phase2: Vec<Rational<T>> = (0..width)
+        .map(|j| {
+            if j < columns {
+                c[j].clone()
+            } else {
+                Rational::zero()
+            }
+        })
+        .collect();
+    for r in 0..rows {
+        if basis[r] < columns {
+            let cost = c[basis[r]].clone();
+            for j in 0..width {
+                phase2[j] = phase2[j].clone() - cost.clone() * tableau[r][j].clone();
+            }
+        }
+    }
+    tableau[rows] = phase2;
+    pivot_until_optimal(&mut tableau, &mut basis, columns)?;
+
+    let mut x = vec![Rational::zero(); columns];
+    for r in 0..rows {
+        if basis[r] < columns {
+            x[basis[r]] = tableau[r][width - 1].clone();
+        }
+    }
+    Ok((-tableau[rows][width - 1].clone(), x))
+}
+
+fn pivot_until_optimal<T: Clone + Integer + Signed>(
+    tableau: &mut [Vec<Rational<T>>],
+    basis: &mut [usize],
+    entering_limit: usize,
+) -> Result<(), ProgramError> {
+    let rows = basis.len();
+    let last = tableau[0].len() - 1;
+    while let Some(column) = (0..entering_limit).find(|&j| tableau[rows][j].is_negative()) {
+        let mut leaving: Option<(usize, Rational<T>)> = None;
+        for r in 0..rows {
+            if !tableau[r][column].is_positive() {
+                continue;
+            }
+            let ratio = tableau[r][last].clone() / tableau[r][column].clone();
+            let better = match &leaving {
+                None => true,
+                Some((l, best)) => ratio < *best || (ratio == *best && basis[r] < basis[*l]),
+            };
+            if better {
+                leaving = Some((r, ratio));
+            }
+        }
+        let Some((row, _)) = leaving else {
+            return Err(ProgramError::Unbounded);
+        };
+        pivot(tableau, basis, row, column);
+    }
+    Ok(())
+}
+
+fn pivot<T: Clone + Integer + Signed>(
+    tableau: &mut [Vec<Rational<T>>],
+    basis: &mut [usize],
+    row: usize,
+    column: usize,
+) {
+    let factor = tableau[row][column].clone();
+    tableau[row] = tableau[row]
+        .iter()
+        .map(|v| v.clone() / factor.clone())
+        .collect();
+    for r in 0..tableau.len() {
+        if r == row || tableau[r][column].is_zero() {
+            continue;
+        }
+        let multiple = tableau[r][column].clone();
+        for c in 0..tableau[r].len() {
+            if tableau[row][c].is_zero() {
+                continue;
+            }
+            let delta = multiple.clone() * tableau[row][c].clone();
+            tableau[r][c] = tableau[r][c].clone() - delta;
+        }
+    }
+    basis[row] = column;
+}
+
+/// Integer linear program minimizing `objective * x` over non-negative integers with equality
+/// constraints and optional upper bounds, solved by branch and bound on simplex relaxations.
+#[derive(Clone, Debug)]
+pub struct IntegerProgram<T = i128> {
+    objective: Vec<T>,
+    constraints: Vec<(Vec<T>, T)>,
+    upper: Vec<Option<T>>,
+}
+
+impl<T: Clone + Integer + Signed> IntegerProgram<T> {
+    pub fn new(objective: Vec<T>) -> Self {
+        let variables = objective.len();
+        Self {
+            objective,
+            constraints: Vec::new(),
+            upper: vec![None; variables],
+        }
+    }
+
+    pub fn equal(&mut self, coefficients: Vec<T>, value: T) {
+        self.constraints.push((coefficients, value));
+    }
+
+    pub fn upper_bound(&mut self, variable: usize, bound: T) {
+        self.upper[variable] = Some(bound);
+    }
+
+    pub fn minimize(&self) -> Result<(T, Vec<T>), ProgramError> {
+        let variables = self.objective.len();
+        let mut best: Option<(T, Vec<T>)> = None;
+        let mut stack = vec![(vec![T::zero(); variables], self.upper.clone())];
+        while let Some((lower, upper)) = stack.pop() {
+            let (value, x) = match self.relaxation(&lower, &upper) {
+                Ok(solution) => solution,
+                Err(ProgramError::Infeasible) => continue,
+                Err(e) => return Err(e),
+            };
+            if best.as_ref().is_some_and(|(b, _)| value.ceil() >= *b) {
+                continue;
+            }
+            match x.iter().position(|v| !v.is_integer()) {
+                None => best = Some((value.ceil(), integer_solution(&x).unwrap())),
+                Some(j) => {
+                    let mut above = lower.clone();
+                    above[j] = x[j].ceil();
+                    stack.push((above, upper.clone()));
+                    let mut below = upper;
+                    below[j] = Some(x[j].floor());
+                    stack.push((lower, below));
+                }
+            }
+        }
+        best.ok_or(ProgramError::Infeasible)
+    }
+
+    fn relaxation(
+        &self,
+        lower: &[T],
+        upper: &[Option<T>],
+    ) -> Result<(Rational<T>, Vec<Rational<T>>), ProgramError> {
+        // shift every variable by its lower bound and add a slack per upper bound
+        let variables = self.objective.len();
+        let bounded: Vec<usize> = (0..variables).filter(|&j| upper[j].is_some()).collect();
+        let columns = variables + bounded.len();
+        let mut a = Vec::new();
+        let mut b = Vec::new();
+        for (coefficients, value) in &self.constraints {
+            let mut row: Vec<Rational<T>> =
+                coefficients.iter().cloned().map(Rational::from).collect();
+            row.resize(columns, Rational::zero());
+            let shift = (0..variables).fold(T::zero(), |acc, j| {
+                acc + coefficients[j].clone() * lower[j].clone()
+            });
+            a.push(row);
+            b.push(Rational::from(value.clone() - shift));
+        }
+        for (s, &j) in bounded.iter().enumerate() {
+            let bound = upper[j].clone().unwrap() - lower[j].clone();
+            if bound.is_negative() {
+                return Err(ProgramError::Infeasible);
+            }
+            let mut row = vec![Rational::zero(); columns];
+            row[j] = Rational::one();
+            row[variables + s] = Rational::one();
+            a.push(row);
+            b.push(Rational::from(bound));
+        }
+        let mut c: Vec<Rational<T>> = self.objective.iter().cloned().map(Rational::from).collect();
+        c.resize(columns, Rational::zero());
+        let (value, y) = simplex(&a, &b, &c)?;
+        let offset = (0..variables).fold(T::zero(), |acc, j| {
+            acc + self.objective[j].clone() * lower[j].clone()
+        });
+        let x = (0..variables)
+            .map(|j| y[j].clone() + Rational::from(lower[j].clone()))
+            .collect();
+        Ok((value + Rational::from(offset), x))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn r(n: i128) -> Rational {
+        Rational::from(n)
+    }
+
+    #[test]
+    fn solves_square_systems() {
+        let a = vec![vec![r(94), r(22)], vec![r(34), r(67)]];
+        let solution = solve_linear_system(&a, &[r(8400), r(5400)]);
+        assert_eq!(solution, LinearSolution::Unique(vec![r(80), r(40)]));
+        let a = vec![vec![r(1), r(1)], vec![r(2), r(2)]];
+        assert_eq!(
+            solve_linear_system(&a, &[r(1), r(3)]),
+            LinearSolution::Inconsistent
+        );
+    }
+
+    #[test]
+    fn detects_free_variables() {
+        let a = vec![vec![r(1), r(2), r(1)], vec![r(2), r(4), r(0)]];
+        let solution = solve_linear_system(&a, &[r(3), r(4)]);
+        assert_eq!(
+            solution,
+            LinearSolution::Underdetermined {
+                particular: vec![r(2), r(0), r(1)],
+                free: vec![1],
+            }
+        );
+    }
+
+    #[test]
+    fn minimizes_integer_programs() {
+        // fewest presses of (3) (1,3) (2) (2,3) (0,2) (0,1) to reach {3,5,4,7}
+        let buttons: Vec<Vec<usize>> = vec![
+            vec![3],
+            vec![1, 3],
+            vec![2],
+            vec![2, 3],
+            vec![0, 2],
+            vec![0, 1],
+        ];
+        let mut program = IntegerProgram::new(vec![1; buttons.len()]);
+        for (counter, target) in [3, 5, 4, 7].into_iter().enumerate() {
+            program.equal(
+                buttons
+                    .iter()
+                    .map(|b| b.contains(&counter) as i128)
+                    .collect(),
+                target,
+            );
+        }
+        assert_eq!(program.minimize().unwrap().0, 10);
+    }
+}
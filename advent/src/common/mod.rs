@@ -0,0 +1,13 @@
+// shared helpers, not every solver uses every part of them
+#![allow(dead_code)]
+
+pub mod circuit;
+pub mod cuboid;
+pub mod intervals;
+pub mod linear;
+pub mod modular;
+pub mod parallel;
+pub mod point_cloud;
+pub mod rational;
+pub mod search;
+pub mod vec3;
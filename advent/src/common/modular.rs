@@ -0,0 +1,32 @@
+use num_integer::Integer;
+
+/// Combines congruences `x = residue (mod modulus)` into a single one, the moduli need not be
+/// coprime. Returns `None` when the congruences contradict each other.
+pub fn crt(congruences: impl IntoIterator<Item = (i128, i128)>) -> Option<(i128, i128)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(r1, m1), (r2, m2)| {
+            let e = m1.extended_gcd(&m2);
+            let diff = r2 - r1;
+            if diff % e.gcd != 0 {
+                return None;
+            }
+            let step = m2 / e.gcd;
+            let lcm = m1 * step;
+            let k = (diff / e.gcd % step * (e.x % step)).rem_euclid(step);
+            Some(((r1 + m1 * k).rem_euclid(lcm), lcm))
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn combines_congruences() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+}
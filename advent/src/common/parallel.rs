@@ -0,0 +1,82 @@
+/// Maps `f` over `items`, spread over every available core when the `parallel` feature is
+/// enabled. Results keep the order of `items` either way, so solvers stay deterministic.
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    map_chunks(items, |chunk| chunk.iter().map(&f).collect::<Vec<_>>())
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Maps `f` over consecutive chunks of `items`, one chunk per core when the `parallel` feature
+/// is enabled and a single chunk holding everything otherwise. Lets workers combine their own
+/// results before handing them back, results of the chunks keep the order of `items`.
+pub fn map_chunks<T: Sync, R: Send>(items: &[T], f: impl Fn(&[T]) -> R + Sync) -> Vec<R> {
+    #[cfg(feature = "parallel")]
+    {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        if threads > 1 && items.len() > 1 {
+            let chunk_size = items.len().div_ceil(threads);
+            // workers poll the same token as the solver that spawned them
+            let token = crate::cancel::current();
+            return std::thread::scope(|scope| {
+                let (f, token) = (&f, &token);
+                let workers: Vec<_> = items
+                    .chunks(chunk_size)
+                    .map(|chunk| {
+                        scope.spawn(move || {
+                            if let Some(token) = token {
+                                token.install();
+                            }
+                            f(chunk)
+                        })
+                    })
+                    .collect();
+                workers
+                    .into_iter()
+                    .map(|worker| worker.join().unwrap())
+                    .collect()
+            });
+        }
+    }
+    vec![f(items)]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cancel::{self, CancellationToken};
+
+    #[test]
+    fn keeps_the_order_of_items() {
+        let items: Vec<usize> = (0..1000).collect();
+        assert_eq!(
+            map(&items, |i| i * i),
+            items.iter().map(|i| i * i).collect::<Vec<_>>()
+        );
+        assert_eq!(map(&[] as &[usize], |i| *i), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn combines_chunks_in_order() {
+        let items: Vec<usize> = (0..1000).collect();
+        let sums = map_chunks(&items, |chunk| chunk.iter().sum::<usize>());
+        assert_eq!(sums.iter().sum::<usize>(), 499_500);
+        #[cfg(not(feature = "parallel"))]
+        assert_eq!(sums, vec![499_500]);
+        let firsts = map_chunks(&items, |chunk| chunk[0]);
+        assert!(firsts.is_sorted());
+    }
+
+    #[test]
+    fn workers_see_the_cancellation_of_the_caller() {
+        let token = CancellationToken::new();
+        let caller = token.clone();
+        let seen = std::thread::spawn(move || {
+            caller.install();
+            caller.cancel();
+            map(&[1, 2, 3, 4], |_| cancel::cancelled())
+        });
+        assert_eq!(seen.join().unwrap(), vec![true; 4]);
+        assert!(token.is_cancelled());
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashSet;
+
+use crate::common::vec3::{Rotation, Vec3};
+
+/// Rigid motion rotating points around the origin then moving them by `translation`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Transform {
+    pub rotation: Rotation,
+    pub translation: Vec3,
+}
+
+impl Transform {
+    pub fn apply(&self, point: Vec3) -> Vec3 {
+        self.rotation.apply(point) + self.translation
+    }
+}
+
+/// Set of points along with, for every point, the squared distances to all the other ones.
+/// Those fingerprints survive any rotation and translation, so two clouds seen from different
+/// places can be matched point by point before trying rotations.
+#[derive(Clone, Debug)]
+pub struct PointCloud {
+    points: Vec<Vec3>,
+    fingerprints: Vec<HashSet<i64>>,
+}
+
+impl PointCloud {
+    pub fn new(points: Vec<Vec3>) -> Self {
+        let fingerprints = points
+            .iter()
+            .map(|&p| {
+                points
+                    .iter()
+                    .filter(|&&q| q != p)
+                    .map(|&q| p.distance_squared(q))
+                    .collect()
+            })
+            .collect();
+        Self {
+            points,
+            fingerprints,
+        }
+    }
+
+    pub fn points(&self) -> &[Vec3] {
+        &self.points
+    }
+
+    pub fn transformed(&self, transform: &Transform) -> Self {
+        Self {
+            points: self.points.iter().map(|&p| transform.apply(p)).collect(),
+            fingerprints: self.fingerprints.clone(),
+        }
+    }
+
+    /// Finds the transform bringing at least `min_matches` points of `other` onto points of
+    /// `self`. Only pairs of points whose fingerprints share enough distances are used as
+    /// anchors for the 24 rotations.
+    pub fn align(&self, other: &Self, min_matches: usize) -> Option<Transform> {
+        let shared = min_matches.saturating_sub(1);
+        let own: HashSet<Vec3> = self.points.iter().copied().collect();
+        let mut tried = HashSet::new();
+        for (i, fi) in self.fingerprints.iter().enumerate() {
+            for (j, fj) in other.fingerprints.iter().enumerate() {
+                if fi.intersection(fj).count() < shared {
+                    continue;
+                }
+                for rotation in Rotation::all() {
+                    let transform = Transform {
+                        rotation,
+                        translation: self.points[i] - rotation.apply(other.points[j]),
+                    };
+                    if !tried.insert(transform) {
+                        continue;
+                    }
+                    let matches = other
+                        .points
+                        .iter()
+                        .filter(|&&p| own.contains(&transform.apply(p)))
+                        .count();
+                    if matches >= min_matches {
+                        return Some(transform);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn aligns_rotated_and_moved_clouds() {
+        let points = vec![
+            Vec3::new(0, 0, 0),
+            Vec3::new(5, 1, 2),
+            Vec3::new(-3, 7, 4),
+            Vec3::new(8, -2, 9),
+            Vec3::new(1, 1, -6),
+        ];
+        let transform = Transform {
+            rotation: Rotation::all()[13],
+            translation: Vec3::new(100, -20, 7),
+        };
+        let reference = PointCloud::new(points.clone());
+        let mut seen = points
+            .iter()
+            .map(|&p| transform.apply(p))
+            .collect::<Vec<_>>();
+        seen.push(Vec3::new(50, 50, 50));
+        let seen = PointCloud::new(seen);
+        let found = reference.align(&seen, 5).unwrap();
+        for &p in &points {
+            assert_eq!(found.apply(transform.apply(p)), p);
+        }
+        assert_eq!(reference.align(&seen, 6), None);
+    }
+}
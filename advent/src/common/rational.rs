@@ -0,0 +1,206 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::iter::Sum;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num_integer::Integer;
+use num_traits::{One, Signed};
+
+/// Exact fraction kept in lowest terms with a positive denominator.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rational<T = i128> {
+    numer: T,
+    denom: T,
+}
+
+impl<T: Clone + Integer + Signed> Rational<T> {
+    pub fn new(numer: T, denom: T) -> Self {
+        assert!(!denom.is_zero(), "rational with zero denominator");
+        let gcd = numer.gcd(&denom);
+        let sign = if denom.is_negative() {
+            -T::one()
+        } else {
+            T::one()
+        };
+        Self {
+            numer: sign.clone() * numer / gcd.clone(),
+            denom: sign * denom / gcd,
+        }
+    }
+
+    pub fn integer(value: T) -> Self {
+        Self {
+            numer: value,
+            denom: T::one(),
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::integer(T::zero())
+    }
+
+    pub fn one() -> Self {
+        Self::integer(T::one())
+    }
+
+    pub fn numer(&self) -> &T {
+        &self.numer
+    }
+
+    pub fn denom(&self) -> &T {
+        &self.denom
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numer.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.numer.is_negative()
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.numer.is_positive()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom.is_one()
+    }
+
+    pub fn to_integer(&self) -> Option<T> {
+        self.is_integer().then(|| self.numer.clone())
+    }
+
+    pub fn floor(&self) -> T {
+        self.numer.div_floor(&self.denom)
+    }
+
+    pub fn ceil(&self) -> T {
+        -((-self.numer.clone()).div_floor(&self.denom))
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            numer: self.numer.abs(),
+            denom: self.denom.clone(),
+        }
+    }
+
+    pub fn recip(&self) -> Self {
+        Self::new(self.denom.clone(), self.numer.clone())
+    }
+}
+
+impl<T: Clone + Integer + Signed> From<T> for Rational<T> {
+    fn from(value: T) -> Self {
+        Self::integer(value)
+    }
+}
+
+impl<T: Clone + Integer + Signed> Add for Rational<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        if self.denom == rhs.denom {
+            Self::new(self.numer + rhs.numer, self.denom)
+        } else {
+            Self::new(
+                self.numer * rhs.denom.clone() + rhs.numer * self.denom.clone(),
+                self.denom * rhs.denom,
+            )
+        }
+    }
+}
+
+impl<T: Clone + Integer + Signed> Sub for Rational<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<T: Clone + Integer + Signed> Mul for Rational<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        // cross-cancel first so i128 products stay small
+        let left = self.numer.gcd(&rhs.denom);
+        let right = rhs.numer.gcd(&self.denom);
+        Self::new(
+            (self.numer / left.clone()) * (rhs.numer / right.clone()),
+            (self.denom / right) * (rhs.denom / left),
+        )
+    }
+}
+
+impl<T: Clone + Integer + Signed> Div for Rational<T> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Self::mul(self, rhs.recip())
+    }
+}
+
+impl<T: Clone + Integer + Signed> Neg for Rational<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            numer: -self.numer,
+            denom: self.denom,
+        }
+    }
+}
+
+impl<T: Clone + Integer + Signed> Sum for Rational<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, r| acc + r)
+    }
+}
+
+impl<T: Clone + Integer + Signed> Ord for Rational<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numer.clone() * other.denom.clone()).cmp(&(other.numer.clone() * self.denom.clone()))
+    }
+}
+
+impl<T: Clone + Integer + Signed> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Display + One + PartialEq> Display for Rational<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.denom.is_one() {
+            f.write_fmt(format_args!("{}", self.numer))
+        } else {
+            f.write_fmt(format_args!("{}/{}", self.numer, self.denom))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn keeps_lowest_terms() {
+        let r = Rational::new(6i128, -4);
+        assert_eq!((*r.numer(), *r.denom()), (-3, 2));
+        assert_eq!(r.floor(), -2);
+        assert_eq!(r.ceil(), -1);
+        assert_eq!(r.clone() + Rational::new(3, 2), Rational::zero());
+        assert_eq!(r.clone() * r.recip(), Rational::one());
+        assert!(r < Rational::integer(-1));
+    }
+
+    #[test]
+    fn works_with_big_integers() {
+        let big = Rational::integer(BigInt::from(i128::MAX)) * Rational::integer(BigInt::from(4));
+        let back = big / Rational::new(BigInt::from(8), BigInt::from(2));
+        assert_eq!(back.to_integer(), Some(BigInt::from(i128::MAX)));
+    }
+}
@@ -0,0 +1,290 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use std::iter::Sum;
+use std::ops::Add;
+
+use crate::cancel;
+
+/// Optimisation problem explored by [`branch_and_bound`], which looks for the node with the
+/// greatest value; minimising problems can use `Reverse` values.
+pub trait BranchAndBound {
+    type Node: Clone;
+    type Key: Hash + Eq;
+    type Value: Ord + Copy;
+
+    fn successors(&self, node: &Self::Node) -> Vec<Self::Node>;
+
+    /// Value of the node taken as a solution, `None` when it is not one.
+    fn value(&self, node: &Self::Node) -> Option<Self::Value>;
+
+    /// Optimistic value, no node reachable from `node` can do better.
+    fn bound(&self, node: &Self::Node) -> Self::Value;
+
+    /// Nodes sharing a key are compared with [`BranchAndBound::dominates`].
+    fn key(&self, node: &Self::Node) -> Self::Key;
+
+    /// Whether everything reachable from `other` can be done as well from `node`. By default
+    /// nodes sharing a key are plain duplicates.
+    fn dominates(&self, _node: &Self::Node, _other: &Self::Node) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    /// Explores the most promising child first, keeping memory low.
+    DepthFirst,
+    /// Always expands the node with the best bound, stopping as soon as no node can beat the
+    /// best solution found.
+    BestFirst,
+}
+
+#[derive(Debug)]
+pub struct SearchOutcome<N, V> {
+    pub best: Option<(V, N)>,
+    pub expanded: usize,
+    pub pruned: usize,
+}
+
+impl<N, V: Copy> SearchOutcome<N, V> {
+    pub fn value(&self) -> Option<V> {
+        self.best.as_ref().map(|(v, _)| *v)
+    }
+
+    pub fn stats(&self) -> SearchStats {
+        SearchStats {
+            expanded: self.expanded,
+            pruned: self.pruned,
+        }
+    }
+}
+
+/// Work done by one or more searches, summed over every search a solver runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    pub expanded: usize,
+    pub pruned: usize,
+}
+
+impl Add for SearchStats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            expanded: self.expanded + other.expanded,
+            pruned: self.pruned + other.pruned,
+        }
+    }
+}
+
+impl Sum for SearchStats {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+impl Display for SearchStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} nodes expanded, {} pruned",
+            self.expanded, self.pruned
+        )
+    }
+}
+
+struct Candidate<N, V> {
+    bound: V,
+    order: usize,
+    node: N,
+}
+
+impl<N, V: Ord> Ord for Candidate<N, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // older nodes first among equal bounds
+        self.bound
+            .cmp(&other.bound)
+            .then(other.order.cmp(&self.order))
+    }
+}
+
+impl<N, V: Ord> PartialOrd for Candidate<N, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, V: Ord> PartialEq for Candidate<N, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, V: Ord> Eq for Candidate<N, V> {}
+
+pub fn branch_and_bound<P: BranchAndBound>(
+    problem: &P,
+    start: P::Node,
+    strategy: Strategy,
+) -> SearchOutcome<P::Node, P::Value> {
+    let mut outcome = SearchOutcome {
+        best: None,
+        expanded: 0,
+        pruned: 0,
+    };
+    let mut seen: HashMap<P::Key, Vec<P::Node>> = HashMap::new();
+    let mut stack: Vec<Candidate<P::Node, P::Value>> = Vec::new();
+    let mut heap: BinaryHeap<Candidate<P::Node, P::Value>> = BinaryHeap::new();
+    let mut order = 0;
+
+    let mut push = |node: P::Node,
+                    outcome: &mut SearchOutcome<P::Node, P::Value>,
+                    stack: &mut Vec<_>,
+                    heap: &mut BinaryHeap<_>| {
+        if let Some(value) = problem.value(&node)
+            && outcome.value().is_none_or(|best| value > best)
+        {
+            outcome.best = Some((value, node.clone()));
+        }
+        let bound = problem.bound(&node);
+        if outcome.value().is_some_and(|best| bound <= best) {
+            outcome.pruned += 1;
+            return;
+        }
+        match seen.entry(problem.key(&node)) {
+            Entry::Occupied(mut entry) => {
+                if entry.get().iter().any(|s| problem.dominates(s, &node)) {
+                    outcome.pruned += 1;
+                    return;
+                }
+                entry.get_mut().retain(|s| !problem.dominates(&node, s));
+                entry.get_mut().push(node.clone());
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(vec![node.clone()]);
+            }
+        }
+        order += 1;
+        let candidate = Candidate { bound, order, node };
+        match strategy {
+            Strategy::DepthFirst => stack.push(candidate),
+            Strategy::BestFirst => heap.push(candidate),
+        }
+    };
+
+    push(start, &mut outcome, &mut stack, &mut heap);
+    while !cancel::cancelled() {
+        let candidate = match strategy {
+            Strategy::DepthFirst => stack.pop(),
+            Strategy::BestFirst => heap.pop(),
+        };
+        let Some(candidate) = candidate else {
+            break;
+        };
+        if outcome.value().is_some_and(|best| candidate.bound <= best) {
+            outcome.pruned += 1;
+            if strategy == Strategy::BestFirst {
+                outcome.pruned += heap.len();
+                break;
+            }
+            continue;
+        }
+        outcome.expanded += 1;
+        let mut children = problem.successors(&candidate.node);
+        if strategy == Strategy::DepthFirst {
+            // the most promising child ends up on top of the stack
+            children.sort_by_cached_key(|c| problem.bound(c));
+        }
+        for child in children {
+            push(child, &mut outcome, &mut stack, &mut heap);
+        }
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// 0/1 knapsack deciding items in order.
+    struct Knapsack {
+        items: Vec<(u32, u32)>,
+        capacity: u32,
+    }
+
+    #[derive(Clone)]
+    struct Packing {
+        next: usize,
+        weight: u32,
+        value: u32,
+    }
+
+    impl BranchAndBound for Knapsack {
+        type Node = Packing;
+        type Key = (usize, u32);
+        type Value = u32;
+
+        fn successors(&self, node: &Packing) -> Vec<Packing> {
+            let Some(&(weight, value)) = self.items.get(node.next) else {
+                return vec![];
+            };
+            let skip = Packing {
+                next: node.next + 1,
+                ..node.clone()
+            };
+            let mut next = vec![skip];
+            if node.weight + weight <= self.capacity {
+                next.push(Packing {
+                    next: node.next + 1,
+                    weight: node.weight + weight,
+                    value: node.value + value,
+                });
+            }
+            next
+        }
+
+        fn value(&self, node: &Packing) -> Option<u32> {
+            Some(node.value)
+        }
+
+        fn bound(&self, node: &Packing) -> u32 {
+            node.value + self.items[node.next..].iter().map(|i| i.1).sum::<u32>()
+        }
+
+        fn key(&self, node: &Packing) -> (usize, u32) {
+            (node.next, node.weight)
+        }
+
+        fn dominates(&self, node: &Packing, other: &Packing) -> bool {
+            node.value >= other.value
+        }
+    }
+
+    #[test]
+    fn solves_knapsack_with_both_strategies() {
+        let knapsack = Knapsack {
+            items: vec![(12, 4), (2, 2), (1, 1), (1, 2), (4, 10)],
+            capacity: 15,
+        };
+        let start = Packing {
+            next: 0,
+            weight: 0,
+            value: 0,
+        };
+        for strategy in [Strategy::DepthFirst, Strategy::BestFirst] {
+            let outcome = branch_and_bound(&knapsack, start.clone(), strategy);
+            assert_eq!(outcome.value(), Some(15));
+            assert!(outcome.pruned > 0);
+            let stats = outcome.stats();
+            let line = format!(
+                "{} nodes expanded, {} pruned",
+                outcome.expanded, outcome.pruned
+            );
+            assert_eq!(stats.to_string(), line);
+            assert_eq!(outcome.best.unwrap().1.weight, 8);
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use std::fmt::{Debug, Formatter};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use num_traits::{Num, Signed, ToPrimitive, Zero};
+
+/// Point or displacement in 3D space.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec3<T = i64> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Copy + Num> Vec3<T> {
+    pub fn zero() -> Self {
+        Self::new(T::zero(), T::zero(), T::zero())
+    }
+
+    pub fn to_array(self) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// Applies `f` to every coordinate.
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Vec3<U> {
+        Vec3::new(f(self.x), f(self.y), f(self.z))
+    }
+
+    pub fn dot(self, rhs: Self) -> T {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    pub fn cross(self, rhs: Self) -> Self {
+        Self::new(
+            self.y * rhs.z - self.z * rhs.y,
+            self.z * rhs.x - self.x * rhs.z,
+            self.x * rhs.y - self.y * rhs.x,
+        )
+    }
+
+    pub fn norm_squared(self) -> T {
+        self.dot(self)
+    }
+
+    /// Distance squared, exact for integer coordinates.
+    pub fn distance_squared(self, other: Self) -> T {
+        (self - other).norm_squared()
+    }
+
+    /// The six points sharing a face with this one.
+    pub fn neighbors(self) -> [Self; 6] {
+        let one = T::one();
+        [
+            Self::new(self.x - one, self.y, self.z),
+            Self::new(self.x + one, self.y, self.z),
+            Self::new(self.x, self.y - one, self.z),
+            Self::new(self.x, self.y + one, self.z),
+            Self::new(self.x, self.y, self.z - one),
+            Self::new(self.x, self.y, self.z + one),
+        ]
+    }
+}
+
+impl<T: Copy + Num + ToPrimitive> Vec3<T> {
+    pub fn norm(self) -> f64 {
+        self.norm_squared().to_f64().unwrap_or(f64::NAN).sqrt()
+    }
+
+    pub fn euclidean_distance(self, other: Self) -> f64 {
+        (self - other).norm()
+    }
+}
+
+impl<T: Copy + Signed> Vec3<T> {
+    /// Sum of the absolute coordinates.
+    pub fn manhattan(self) -> T {
+        self.x.abs() + self.y.abs() + self.z.abs()
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> T {
+        (self - other).manhattan()
+    }
+
+    pub fn signum(self) -> Self {
+        self.map(|c| c.signum())
+    }
+}
+
+impl<T> From<[T; 3]> for Vec3<T> {
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Vec3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vec3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vec3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl<T: Zero + Add<Output = T>> Sum for Vec3<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(T::zero(), T::zero(), T::zero()), |a, v| a + v)
+    }
+}
+
+impl<T: Debug> Debug for Vec3<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("<{:?},{:?},{:?}>", self.x, self.y, self.z))
+    }
+}
+
+/// One of the 24 rotations of the cube, as a signed permutation matrix with determinant 1.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation {
+    matrix: [[i8; 3]; 3],
+}
+
+impl Rotation {
+    pub const IDENTITY: Self = Self {
+        matrix: [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+    };
+
+    /// All 24 rotations, starting with the identity.
+    pub fn all() -> Vec<Self> {
+        let permutations = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+        let mut rotations = Vec::with_capacity(24);
+        for (p, permutation) in permutations.iter().enumerate() {
+            for signs in 0..8 {
+                let mut matrix = [[0; 3]; 3];
+                for row in 0..3 {
+                    matrix[row][permutation[row]] = if signs & (1 << row) == 0 { 1 } else { -1 };
+                }
+                // odd permutations need an odd number of flips to keep the determinant positive
+                if (signs as u32).count_ones() % 2 == [0, 1, 1, 0, 0, 1][p] {
+                    rotations.push(Self { matrix });
+                }
+            }
+        }
+        rotations
+    }
+
+    pub fn matrix(&self) -> [[i8; 3]; 3] {
+        self.matrix
+    }
+
+    pub fn apply<T: Copy + Zero + Neg<Output = T>>(&self, v: Vec3<T>) -> Vec3<T> {
+        let c = [v.x, v.y, v.z];
+        let row = |r: [i8; 3]| {
+            (0..3).fold(T::zero(), |acc, i| match r[i] {
+                1 => acc + c[i],
+                -1 => acc + -c[i],
+                _ => acc,
+            })
+        };
+        Vec3::new(
+            row(self.matrix[0]),
+            row(self.matrix[1]),
+            row(self.matrix[2]),
+        )
+    }
+
+    /// Rotation applying `self` and then `other`.
+    pub fn then(&self, other: &Self) -> Self {
+        let mut matrix = [[0; 3]; 3];
+        for r in 0..3 {
+            for c in 0..3 {
+                matrix[r][c] = (0..3).map(|k| other.matrix[r][k] * self.matrix[k][c]).sum();
+            }
+        }
+        Self { matrix }
+    }
+
+    pub fn inverse(&self) -> Self {
+        let mut matrix = [[0; 3]; 3];
+        for r in 0..3 {
+            for c in 0..3 {
+                matrix[r][c] = self.matrix[c][r];
+            }
+        }
+        Self { matrix }
+    }
+}
+
+impl Debug for Rotation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:?}", self.matrix))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn does_vector_arithmetic() {
+        let a = Vec3::new(1i64, -2, 3);
+        let b = Vec3::new(4, 0, -1);
+        assert_eq!(a + b, Vec3::new(5, -2, 2));
+        assert_eq!(a - b, Vec3::new(-3, -2, 4));
+        assert_eq!(a * 2, Vec3::new(2, -4, 6));
+        assert_eq!(a.dot(b), 1);
+        assert_eq!(a.cross(b), Vec3::new(2, 13, 8));
+        assert_eq!(a.manhattan(), 6);
+        assert_eq!(a.manhattan_distance(b), 9);
+        assert_eq!(Vec3::new(3, 4, 0).norm(), 5.0);
+    }
+
+    #[test]
+    fn builds_the_rotation_group() {
+        let rotations = Rotation::all();
+        assert_eq!(rotations.len(), 24);
+        assert_eq!(rotations[0], Rotation::IDENTITY);
+        let v = Vec3::new(1, 2, 3);
+        let images: HashSet<_> = rotations.iter().map(|r| r.apply(v)).collect();
+        assert_eq!(images.len(), 24);
+        for a in &rotations {
+            assert_eq!(a.then(&a.inverse()), Rotation::IDENTITY);
+            for b in &rotations {
+                assert!(rotations.contains(&a.then(b)));
+                assert_eq!(b.apply(a.apply(v)), a.then(b).apply(v));
+            }
+        }
+    }
+}
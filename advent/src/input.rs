@@ -0,0 +1,202 @@
+use std::env::var_os;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+
+use crate::options::AdventError;
+
+const INPUT_DIR_VARIABLE: &str = "ADVENT_INPUT_DIR";
+const CONFIG_VARIABLE: &str = "ADVENT_CONFIG";
+const DEFAULT_INPUT_DIR: &str = "input";
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Where inputs live, read from the first config file found among `$ADVENT_CONFIG`,
+/// `./advent.json` and `~/.config/advent/config.json`:
+///
+/// ```json
+/// { "input_dir": "/data/advent", "profiles": { "alice": "/home/alice/advent" } }
+/// ```
+#[derive(Debug, Default)]
+pub struct Config {
+    input_dir: Option<PathBuf>,
+    profiles: Vec<(String, PathBuf)>,
+}
+
+impl Config {
+    pub fn load() -> Result<Self, AdventError> {
+        let candidates = [
+            var_os(CONFIG_VARIABLE).map(PathBuf::from),
+            Some(PathBuf::from("advent.json")),
+            var_os("HOME").map(|home| Path::new(&home).join(".config/advent/config.json")),
+        ];
+        match candidates.into_iter().flatten().find(|p| p.is_file()) {
+            Some(path) => {
+                let text = read_path(&path)
+                    .map_err(|e| AdventError::InvalidConfig(format!("{}: {e}", path.display())))?;
+                Self::parse(&text)
+                    .map_err(|e| AdventError::InvalidConfig(format!("{}: {e}", path.display())))
+            }
+            None => Ok(Self::default()),
+        }
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let value = json::parse(text).map_err(|e| e.to_string())?;
+        let input_dir = match &value["input_dir"] {
+            v if v.is_null() => None,
+            v => Some(PathBuf::from(
+                v.as_str().ok_or("input_dir is not a string")?,
+            )),
+        };
+        let profiles = value["profiles"]
+            .entries()
+            .map(|(name, dir)| match dir.as_str() {
+                Some(dir) => Ok((name.to_string(), PathBuf::from(dir))),
+                None => Err(format!("profile {name} is not a string")),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            input_dir,
+            profiles,
+        })
+    }
+}
+
+/// Finds the input of a day in the input directory, `$ADVENT_INPUT_DIR` first, then the config
+/// file and finally `./input`. A profile picks its own directory from the config file, or a
+/// sub-directory named after it.
+#[derive(Debug)]
+pub struct InputResolver {
+    dir: PathBuf,
+}
+
+impl InputResolver {
+    pub fn new(config: &Config, profile: Option<&str>) -> Result<Self, AdventError> {
+        Self::with_input_dir(
+            var_os(INPUT_DIR_VARIABLE).map(PathBuf::from),
+            config,
+            profile,
+        )
+    }
+
+    /// Same as [`InputResolver::new`], with `input_dir` standing for `$ADVENT_INPUT_DIR`.
+    fn with_input_dir(
+        input_dir: Option<PathBuf>,
+        config: &Config,
+        profile: Option<&str>,
+    ) -> Result<Self, AdventError> {
+        let base = input_dir
+            .or(config.input_dir.clone())
+            .unwrap_or(PathBuf::from(DEFAULT_INPUT_DIR));
+        let dir = match profile {
+            None => base,
+            Some(name) => match config.profiles.iter().find(|(n, _)| n == name) {
+                Some((_, dir)) => dir.clone(),
+                None if base.join(name).is_dir() => base.join(name),
+                None => {
+                    let known = config.profiles.iter().map(|(n, _)| n.clone()).collect();
+                    return Err(AdventError::UnknownProfile(name.to_string(), known));
+                }
+            },
+        };
+        Ok(Self { dir })
+    }
+
+    /// Plain text first, then gzip-compressed.
+    fn candidates(&self, year: &str, day: &str) -> Vec<PathBuf> {
+        let dir = self.dir.join(format!("year{year}"));
+        vec![
+            dir.join(format!("day{day}.txt")),
+            dir.join(format!("day{day}.txt.gz")),
+        ]
+    }
+
+    pub fn read(&self, year: &str, day: &str) -> Result<String, AdventError> {
+        let candidates = self.candidates(year, day);
+        match candidates.iter().find(|p| p.is_file()) {
+            Some(path) => read_path(path)
+                .map_err(|_| AdventError::FailedToReadFile(path.display().to_string())),
+            None => Err(AdventError::InputNotFound(
+                candidates.iter().map(|p| p.display().to_string()).collect(),
+            )),
+        }
+    }
+}
+
+/// Reads a file, decompressing it when it starts like a gzip stream.
+pub fn read_path(path: &Path) -> std::io::Result<String> {
+    let mut bytes = Vec::new();
+    BufReader::new(File::open(path)?).read_to_end(&mut bytes)?;
+    let mut text = String::new();
+    if bytes.starts_with(&GZIP_MAGIC) {
+        GzDecoder::new(bytes.as_slice()).read_to_string(&mut text)?;
+    } else {
+        text = String::from_utf8(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::fs;
+    use std::io::Write;
+
+    /// Directory removed when the test ends, whether its assertions pass or not.
+    struct TempDir(PathBuf);
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn resolves_profiles_and_compressed_inputs() {
+        let temp =
+            TempDir(std::env::temp_dir().join(format!("advent-input-{}", std::process::id())));
+        let root = &temp.0;
+        let alice = root.join("alice/year2021");
+        let bob = root.join("elsewhere/bob/year2021");
+        fs::create_dir_all(&alice).unwrap();
+        fs::create_dir_all(&bob).unwrap();
+        fs::write(alice.join("day01.txt"), "199\n200\n").unwrap();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"D2FE28\n").unwrap();
+        fs::write(bob.join("day16.txt.gz"), encoder.finish().unwrap()).unwrap();
+
+        let config = Config::parse(&format!(
+            r#"{{ "input_dir": {:?}, "profiles": {{ "bob": {:?} }} }}"#,
+            root.display().to_string(),
+            root.join("elsewhere/bob").display().to_string()
+        ))
+        .unwrap();
+        let alice = InputResolver::with_input_dir(None, &config, Some("alice")).unwrap();
+        assert_eq!(alice.read("2021", "01").unwrap(), "199\n200\n");
+        let bob = InputResolver::with_input_dir(None, &config, Some("bob")).unwrap();
+        assert_eq!(bob.read("2021", "16").unwrap(), "D2FE28\n");
+        match bob.read("2021", "02") {
+            Err(AdventError::InputNotFound(tried)) => {
+                assert_eq!(tried.len(), 2);
+                assert!(tried[1].ends_with("day02.txt.gz"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert!(matches!(
+            InputResolver::with_input_dir(None, &config, Some("carol")),
+            Err(AdventError::UnknownProfile(..))
+        ));
+        // the variable wins over the config file, profiles then look for a sub-directory of it
+        let overridden = Some(root.join("elsewhere"));
+        assert!(InputResolver::with_input_dir(overridden.clone(), &config, Some("alice")).is_err());
+        let bob =
+            InputResolver::with_input_dir(overridden, &Config::default(), Some("bob")).unwrap();
+        assert_eq!(bob.read("2021", "16").unwrap(), "D2FE28\n");
+        assert!(Config::parse(r#"{ "profiles": { "dave": 3 } }"#).is_err());
+    }
+}
@@ -0,0 +1,16 @@
+pub mod cancel;
+pub mod common;
+pub mod solver;
+pub mod visualize;
+pub mod year2015;
+pub mod year2016;
+pub mod year2017;
+pub mod year2018;
+pub mod year2019;
+pub mod year2021;
+pub mod year2022;
+pub mod year2023;
+pub mod year2024;
+pub mod year2025;
+
+pub use solver::{AdventSolver, AdventSolverBuilder, SolverEntry, registry};
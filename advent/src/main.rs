@@ -1,23 +1,24 @@
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 use clap::Parser;
 
 use options::AdventOptions;
 
-use crate::options::AdventError;
+use advent::year2021::day16::Packet;
+use advent::year2023::day20::Advent2023Day20Solver;
+use advent::{registry, visualize};
 
+use crate::memory::CountingAllocator;
+use crate::options::{AdventCommand, AdventError, BitsAction, PulsesAction};
+use crate::runner::{Report, Run, Stage};
+
+mod input;
+mod memory;
 mod options;
-mod solver;
-mod year2015;
-mod year2016;
-mod year2017;
-mod year2018;
-mod year2019;
-mod year2021;
-mod year2022;
-mod year2023;
-mod year2024;
-mod year2025;
+mod runner;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
 macro_rules! time {
     ($p: expr, $s: stmt) => {
@@ -25,7 +26,7 @@ macro_rules! time {
         let now = SystemTime::now();
         $s
         match now.elapsed() {
-            Ok(d) => println!("Duration: {}s {:0>3}.{:0>3}ms", d.as_secs(), d.subsec_millis(), d.subsec_micros() % 1000),
+            Ok(d) => println!("Duration: {}", format_duration(d)),
             Err(_) => println!("Duration errored"),
         }
     }
@@ -34,22 +35,141 @@ macro_rules! time {
 fn main() -> Result<(), AdventError> {
     let options = AdventOptions::parse();
 
+    if let Some(command) = options.command() {
+        return run_command(&options, command);
+    }
+
+    if options.profile_memory() {
+        memory::enable();
+    }
+
+    if options.all() {
+        run_all(&options);
+        return Ok(());
+    }
+
     let (solver_builder, year, day) = options.solver_builder()?;
     println!("Solving year {year} day {day}");
 
     time!("\nReading input", let input = options.read_input(&year, &day)?);
 
-    time!("\nBuilding solver", let solver = solver_builder(&input));
+    if let Some(target) = options.visualize() {
+        time!("\nBuilding solver", let solver = solver_builder(&input));
+        let visualizer = solver
+            .visualizer()
+            .ok_or(AdventError::CannotVisualize(year, day))?;
+        let failed = |e: std::io::Error| AdventError::FailedToVisualize(e.to_string());
+        let mut sink = visualize::frame_sink(target).map_err(failed)?;
+        visualizer.visualize(sink.as_mut()).map_err(failed)?;
+        return sink.finish().map_err(failed);
+    }
 
-    if options.part1() {
-        time!("\nSolving part 1", let solution = solver.solve_part1_string());
-        println!("Solution:\n{solution}");
+    let stages = stages(&options);
+    let run = Run::start(solver_builder, input, stages.clone(), options.timeout());
+    for stage in [Stage::Building].into_iter().chain(stages) {
+        println!("\n{}", stage.name());
+        let report = run.wait(stage)?;
+        println!("Duration: {}", format_duration(report.duration));
+        if options.profile_memory() {
+            println!("Memory: {}", report.memory);
+        }
+        if let Some(solution) = report.solution {
+            println!("Solution:\n{solution}");
+        }
     }
 
+    Ok(())
+}
+
+fn stages(options: &AdventOptions) -> Vec<Stage> {
+    let mut stages = Vec::new();
+    if options.part1() {
+        stages.push(Stage::Part1);
+    }
     if options.part2() {
-        time!("\nSolving part 2", let solution = solver.solve_part2_string());
-        println!("Solution:\n{solution}");
+        stages.push(Stage::Part2);
+    }
+    stages
+}
+
+/// One line for run-all output.
+fn summary(report: &Report, profile_memory: bool) -> String {
+    let mut line = match &report.solution {
+        Some(solution) => format!("{}: {solution} in {}", report.stage.name(), format_duration(report.duration)),
+        None => format!("{} in {}", report.stage.name(), format_duration(report.duration)),
+    };
+    if profile_memory {
+        line.push_str(&format!(", {}", report.memory));
     }
+    line
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{}s {:0>3}.{:0>3}ms", d.as_secs(), d.subsec_millis(), d.subsec_micros() % 1000)
+}
 
+/// Solves every day in turn, a day timing out or failing is reported and the next one goes on.
+fn run_all(options: &AdventOptions) {
+    let stages = stages(options);
+    for entry in registry()
+        .iter()
+        .filter(|e| options.year().is_none_or(|y| y == e.year))
+    {
+        let (year, day) = (entry.year.to_string(), format!("{:02}", entry.day));
+        println!("\nYear {year} day {day}");
+        let input = match options.read_input(&year, &day) {
+            Ok(input) => input,
+            Err(AdventError::InputNotFound(_)) => {
+                println!("  Skipped, no input");
+                continue;
+            }
+            Err(error) => {
+                println!("  {error:?}");
+                continue;
+            }
+        };
+        let run = Run::start(entry.builder, input, stages.clone(), options.timeout());
+        for stage in [Stage::Building].into_iter().chain(stages.iter().copied()) {
+            match run.wait(stage) {
+                Ok(report) => println!("  {}", summary(&report, options.profile_memory())),
+                Err(error) => {
+                    println!("  {error:?}");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn run_command(options: &AdventOptions, command: &AdventCommand) -> Result<(), AdventError> {
+    match command {
+        AdventCommand::Bits { action: BitsAction::Decode { hex } } => {
+            let packet = Packet::decode(hex)
+                .map_err(|e| AdventError::InvalidTransmission(format!("{e:?}")))?;
+            println!("{packet}");
+            println!("\nVersion sum: {}\nValue: {}", packet.version_sum(), packet.value());
+        }
+        AdventCommand::Bits { action: BitsAction::Encode { expression } } => {
+            let packet = expression
+                .parse::<Packet>()
+                .map_err(AdventError::InvalidTransmission)?;
+            let hex = packet
+                .encode()
+                .map_err(|e| AdventError::InvalidTransmission(format!("{e:?}")))?;
+            println!("{hex}");
+        }
+        AdventCommand::Pulses { action } => {
+            let network = Advent2023Day20Solver::new(&options.read_input("2023", "20")?);
+            match action {
+                PulsesAction::Dot => print!("{}", network.to_dot()),
+                PulsesAction::Counters => {
+                    let report = network
+                        .counter_report()
+                        .map_err(|e| AdventError::InvalidNetwork(format!("{e:?}")))?;
+                    print!("{report}");
+                }
+            }
+        }
+    }
     Ok(())
 }
@@ -0,0 +1,150 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering};
+
+/// System allocator keeping count of allocations once [`enable`] is called, for the whole
+/// process. Stages run one after another so the counts of a stage are its own.
+pub struct CountingAllocator;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+// memory allocated before counting started may be freed afterward, only differences matter
+static LIVE: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = unsafe { System.alloc(layout) };
+        if !pointer.is_null() {
+            allocated(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(pointer, layout) };
+        freed(layout.size());
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer = unsafe { System.alloc_zeroed(layout) };
+        if !pointer.is_null() {
+            allocated(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let moved = unsafe { System.realloc(pointer, layout, new_size) };
+        if !moved.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        moved
+    }
+}
+
+fn allocated(size: usize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size as isize, Ordering::Relaxed) + size as isize;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+}
+
+fn freed(size: usize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        LIVE.fetch_sub(size as isize, Ordering::Relaxed);
+    }
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Counters when a stage starts, to be compared with their values when it ends.
+pub struct Snapshot {
+    allocations: usize,
+    allocated: usize,
+    live: isize,
+}
+
+impl Snapshot {
+    /// Starts a stage, forgetting the peak of the previous one.
+    pub fn take() -> Self {
+        let live = LIVE.load(Ordering::Relaxed);
+        PEAK.store(live, Ordering::Relaxed);
+        Self {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            allocated: ALLOCATED.load(Ordering::Relaxed),
+            live,
+        }
+    }
+
+    pub fn usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - self.allocations,
+            allocated: ALLOCATED.load(Ordering::Relaxed) - self.allocated,
+            peak: (PEAK.load(Ordering::Relaxed) - self.live).max(0) as usize,
+        }
+    }
+}
+
+/// Memory used by a stage, the peak being the most bytes it held at once on top of what was
+/// live when it started.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MemoryUsage {
+    pub allocations: usize,
+    pub allocated: usize,
+    pub peak: usize,
+}
+
+impl Display for MemoryUsage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.allocated),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    let units = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", units[unit])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn counts_allocations_and_peak() {
+        enable();
+        let snapshot = Snapshot::take();
+        let big = vec![1u8; 1 << 20];
+        let small: Vec<Box<u64>> = (0..100).map(Box::new).collect();
+        drop(big);
+        let usage = snapshot.usage();
+        drop(small);
+        assert!(usage.allocations >= 102);
+        assert!(usage.allocated >= (1 << 20) + 800);
+        assert!(usage.peak >= 1 << 20);
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(12), "12 B");
+    }
+}
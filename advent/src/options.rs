@@ -1,12 +1,14 @@
 use std::fmt::{Debug, Formatter};
-use std::fs::read_to_string;
 use std::io;
 use std::io::stdin;
-use std::path::MAIN_SEPARATOR;
+use std::path::Path;
+use std::time::Duration;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
-use crate::solver::AdventSolverBuilder;
+use advent::{AdventSolverBuilder, registry};
+
+use crate::input::{Config, InputResolver, read_path};
 
 pub enum AdventError {
     UnknownYear(u16),
@@ -14,6 +16,15 @@ pub enum AdventError {
     InvalidInputOptions,
     FailedToReadStdin,
     FailedToReadFile(String),
+    CannotVisualize(String, String),
+    FailedToVisualize(String),
+    InvalidTransmission(String),
+    InvalidNetwork(String),
+    Timeout(String, Duration),
+    SolverFailed(String),
+    InputNotFound(Vec<String>),
+    UnknownProfile(String, Vec<String>),
+    InvalidConfig(String),
 }
 
 impl Debug for AdventError {
@@ -23,7 +34,16 @@ impl Debug for AdventError {
             AdventError::UnknownDay(year, day) => f.write_fmt(format_args!("Unknown day {day} within year {year}")),
             AdventError::InvalidInputOptions => f.write_fmt(format_args!("Input options are not valid, only one of input, file and stdin can be specified at a time")),
             AdventError::FailedToReadStdin => f.write_fmt(format_args!("Failed to read input from stdin")),
-            AdventError::FailedToReadFile(file) => f.write_fmt(format_args!("Failed to read input from file {file}"))
+            AdventError::FailedToReadFile(file) => f.write_fmt(format_args!("Failed to read input from file {file}")),
+            AdventError::CannotVisualize(year, day) => f.write_fmt(format_args!("Year {year} day {day} has no visualization")),
+            AdventError::FailedToVisualize(error) => f.write_fmt(format_args!("Failed to write visualization: {error}")),
+            AdventError::InvalidTransmission(error) => f.write_fmt(format_args!("Invalid BITS transmission: {error}")),
+            AdventError::InvalidNetwork(error) => f.write_fmt(format_args!("Invalid module network: {error}")),
+            AdventError::Timeout(stage, timeout) => f.write_fmt(format_args!("{stage} timed out after {timeout:?}")),
+            AdventError::SolverFailed(stage) => f.write_fmt(format_args!("{stage} failed, the solver panicked")),
+            AdventError::InputNotFound(tried) => f.write_fmt(format_args!("No input found, tried:\n  {}", tried.join("\n  "))),
+            AdventError::UnknownProfile(name, known) => f.write_fmt(format_args!("Unknown input profile {name}, configured profiles: [{}]", known.join(", "))),
+            AdventError::InvalidConfig(error) => f.write_fmt(format_args!("Invalid config file {error}"))
         }
     }
 }
@@ -61,7 +81,9 @@ pub struct AdventOptions {
     #[arg(
         short = 'f',
         long,
-        help("Specify which input file to use, defaults to input matching year and day")
+        help(
+            "Specify which input file to use, possibly gzipped, defaults to input matching year and day"
+        )
     )]
     file: Option<String>,
     #[arg(short = 'i', long, help("Use string input instead of reading a file"))]
@@ -73,6 +95,83 @@ pub struct AdventOptions {
         help("Read from standard input instead of reading a file")
     )]
     stdin: bool,
+    #[arg(
+        short = 'p',
+        long,
+        help(
+            "Read inputs of this profile, from the config file or a sub-directory of the input directory"
+        )
+    )]
+    profile: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "OUTPUT",
+        help(
+            "Replay the simulation instead of solving, to `terminal`, a .gif file or a directory of PPM images"
+        )
+    )]
+    visualize: Option<String>,
+
+    #[arg(
+        short = 't',
+        long,
+        value_name = "SECONDS",
+        help("Give up on parsing or on a part once it runs for longer than this")
+    )]
+    timeout: Option<f64>,
+    #[arg(
+        short = 'a',
+        long,
+        default_value_t = false,
+        help(
+            "Solve every day, of the given year only if one is given, skipping days without input"
+        )
+    )]
+    all: bool,
+    #[arg(
+        long,
+        default_value_t = false,
+        help("Count allocations, bytes allocated and peak memory of parsing and of each part")
+    )]
+    profile_memory: bool,
+
+    #[command(subcommand)]
+    command: Option<AdventCommand>,
+}
+
+#[derive(Subcommand)]
+pub enum AdventCommand {
+    #[command(about("Encode or decode BITS transmissions from 2021 day 16"))]
+    Bits {
+        #[command(subcommand)]
+        action: BitsAction,
+    },
+    #[command(about("Inspect the pulse module network of 2023 day 20, read like a day's input"))]
+    Pulses {
+        #[command(subcommand)]
+        action: PulsesAction,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum BitsAction {
+    #[command(about("Print a hexadecimal transmission as an S-expression with evaluated values"))]
+    Decode { hex: String },
+    #[command(about(
+        "Print the hexadecimal transmission of an S-expression such as `(sum v1 (literal 2) (literal v3 4))`"
+    ))]
+    Encode { expression: String },
+}
+
+#[derive(Subcommand)]
+pub enum PulsesAction {
+    #[command(about("Print the module network in Graphviz DOT format"))]
+    Dot,
+    #[command(about(
+        "Print each independent counter with its output module, offset, period and phases"
+    ))]
+    Counters,
 }
 
 impl AdventOptions {
@@ -84,6 +183,30 @@ impl AdventOptions {
         self.both_parts() || self.part2
     }
 
+    pub fn visualize(&self) -> Option<&str> {
+        self.visualize.as_deref()
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs_f64)
+    }
+
+    pub fn all(&self) -> bool {
+        self.all
+    }
+
+    pub fn profile_memory(&self) -> bool {
+        self.profile_memory
+    }
+
+    pub fn year(&self) -> Option<u16> {
+        self.year
+    }
+
+    pub fn command(&self) -> Option<&AdventCommand> {
+        self.command.as_ref()
+    }
+
     fn both_parts(&self) -> bool {
         self.part1 == self.part2
     }
@@ -91,9 +214,9 @@ impl AdventOptions {
     pub fn read_input(&self, year: &str, day: &str) -> Result<String, AdventError> {
         match (&self.file, &self.input, &self.stdin) {
             (None, None, true) => read_stdin(),
-            (None, None, false) => read_file(&format!(
-                "input{MAIN_SEPARATOR}year{year}{MAIN_SEPARATOR}day{day}.txt"
-            )),
+            (None, None, false) => InputResolver::new(&Config::load()?, self.profile.as_deref())?
+                .read(year, day)
+                .map(trim),
             (Some(f), None, false) => read_file(f),
             (None, Some(i), false) => Ok(i.to_string()),
             _ => Err(AdventError::InvalidInputOptions),
@@ -106,7 +229,7 @@ impl AdventOptions {
 }
 
 fn read_file(path: &str) -> Result<String, AdventError> {
-    read_to_string(path)
+    read_path(Path::new(path))
         .map(trim)
         .map_err(|_| AdventError::FailedToReadFile(path.to_string()))
 }
@@ -123,10 +246,24 @@ fn trim(input: String) -> String {
     input.trim_end_matches(['\n', '\r']).to_string()
 }
 
-//noinspection RsLiveness
+/// Latest year when none is given, latest solved day within the year when no day is given.
 fn solver_builder(
     year: &Option<u16>,
     day: &Option<u8>,
 ) -> Result<(AdventSolverBuilder, String, String), AdventError> {
-    include!(concat!(env!("OUT_DIR"), "/", "matches.txt"))
+    let registry = registry();
+    let year = match year {
+        Some(y) => *y,
+        None => registry.last().unwrap().year,
+    };
+    let days = registry.iter().filter(|e| e.year == year);
+    let entry = match day {
+        Some(d) => days.clone().find(|e| e.day == *d),
+        None => days.clone().next_back(),
+    };
+    match entry {
+        Some(e) => Ok((e.builder, format!("{year}"), format!("{:02}", e.day))),
+        None if days.count() == 0 => Err(AdventError::UnknownYear(year)),
+        None => Err(AdventError::UnknownDay(year, day.unwrap())),
+    }
 }
@@ -0,0 +1,123 @@
+use std::sync::mpsc::{Receiver, RecvTimeoutError, channel};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use advent::AdventSolverBuilder;
+use advent::cancel::CancellationToken;
+
+use crate::memory::{MemoryUsage, Snapshot};
+use crate::options::AdventError;
+
+/// Solvers used to run on the main thread, keep them clear of its stack size.
+const WORKER_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Stage {
+    Building,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stage::Building => "Building solver",
+            Stage::Part1 => "Solving part 1",
+            Stage::Part2 => "Solving part 2",
+        }
+    }
+}
+
+pub struct Report {
+    pub stage: Stage,
+    pub solution: Option<String>,
+    pub duration: Duration,
+    pub memory: MemoryUsage,
+}
+
+/// Solver built and run on a worker thread, every stage reported as soon as it is done so that
+/// a deadline can be enforced on each one.
+pub struct Run {
+    reports: Receiver<Report>,
+    token: CancellationToken,
+    timeout: Option<Duration>,
+}
+
+impl Run {
+    pub fn start(
+        builder: AdventSolverBuilder,
+        input: String,
+        stages: Vec<Stage>,
+        timeout: Option<Duration>,
+    ) -> Self {
+        let (sender, reports) = channel();
+        let token = CancellationToken::new();
+        let worker = token.clone();
+        thread::Builder::new()
+            .stack_size(WORKER_STACK_SIZE)
+            .spawn(move || {
+                worker.install();
+                let report = |stage, solution, now: Instant, snapshot: Snapshot| {
+                    let duration = now.elapsed();
+                    let memory = snapshot.usage();
+                    !worker.is_cancelled()
+                        && sender
+                            .send(Report {
+                                stage,
+                                solution,
+                                duration,
+                                memory,
+                            })
+                            .is_ok()
+                };
+                let (snapshot, now) = (Snapshot::take(), Instant::now());
+                let solver = builder(&input);
+                if !report(Stage::Building, None, now, snapshot) {
+                    return;
+                }
+                for stage in stages {
+                    let (snapshot, now) = (Snapshot::take(), Instant::now());
+                    let solution = match stage {
+                        Stage::Building => continue,
+                        Stage::Part1 => solver.solve_part1_string(),
+                        Stage::Part2 => solver.solve_part2_string(),
+                    };
+                    if !report(stage, Some(solution), now, snapshot) {
+                        return;
+                    }
+                }
+            })
+            .expect("failed to spawn the solver thread");
+        Self {
+            reports,
+            token,
+            timeout,
+        }
+    }
+
+    /// Waits for `stage`, cancelling the solver when it takes longer than the timeout.
+    pub fn wait(&self, stage: Stage) -> Result<Report, AdventError> {
+        match self.timeout {
+            Some(timeout) => self.reports.recv_timeout(timeout).map_err(|e| match e {
+                RecvTimeoutError::Timeout => {
+                    self.token.cancel();
+                    AdventError::Timeout(stage.name().to_string(), timeout)
+                }
+                RecvTimeoutError::Disconnected => {
+                    AdventError::SolverFailed(stage.name().to_string())
+                }
+            }),
+            None => self
+                .reports
+                .recv()
+                .map_err(|_| AdventError::SolverFailed(stage.name().to_string())),
+        }
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        // a worker still running past this point is never waited for again
+        self.token.cancel();
+    }
+}
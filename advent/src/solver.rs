@@ -1,3 +1,5 @@
+use crate::visualize::Visualize;
+
 pub trait AdventSolver {
     fn solve_part1(&self) -> usize {
         0
@@ -11,6 +13,53 @@ pub trait AdventSolver {
     fn solve_part2_string(&self) -> String {
         self.solve_part2().to_string()
     }
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        None
+    }
 }
 
 pub type AdventSolverBuilder = fn(input: &str) -> Box<dyn AdventSolver>;
+
+/// A solver along with the puzzle it solves.
+pub struct SolverEntry {
+    pub year: u16,
+    pub day: u8,
+    pub name: &'static str,
+    pub builder: AdventSolverBuilder,
+}
+
+impl SolverEntry {
+    pub fn url(&self) -> String {
+        format!("https://adventofcode.com/{}/day/{}", self.year, self.day)
+    }
+}
+
+static REGISTRY: &[SolverEntry] = include!(concat!(env!("OUT_DIR"), "/", "registry.txt"));
+
+/// Every solver, ordered by year then day, generated by the build script from the `yearYYYY`
+/// directories.
+pub fn registry() -> &'static [SolverEntry] {
+    REGISTRY
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lists_every_solver_in_order() {
+        let registry = registry();
+        assert!(
+            registry
+                .windows(2)
+                .all(|w| (w[0].year, w[0].day) < (w[1].year, w[1].day))
+        );
+        let entry = registry
+            .iter()
+            .find(|e| (e.year, e.day) == (2021, 16))
+            .unwrap();
+        assert_eq!(entry.name, "Advent2021Day16Solver");
+        assert_eq!(entry.url(), "https://adventofcode.com/2021/day/16");
+        assert_eq!((entry.builder)("D2FE28").solve_part2(), 2021);
+    }
+}
@@ -0,0 +1,380 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fs::{File, create_dir_all};
+use std::io::{BufWriter, Write, stdout};
+use std::path::PathBuf;
+use std::thread::sleep;
+use std::time::Duration;
+
+pub type Color = [u8; 3];
+
+pub const BLACK: Color = [0, 0, 0];
+pub const GRAY: Color = [110, 110, 110];
+pub const RED: Color = [220, 50, 47];
+pub const GREEN: Color = [80, 200, 80];
+pub const BLUE: Color = [40, 110, 230];
+pub const LIGHT_BLUE: Color = [140, 200, 250];
+pub const YELLOW: Color = [240, 200, 40];
+pub const BROWN: Color = [150, 100, 50];
+
+/// Image of a simulation step, one pixel per grid cell.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize, background: Color) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, color: impl Fn(usize, usize) -> Color) -> Self {
+        Self {
+            width,
+            height,
+            pixels: (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| color(x, y))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Color {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Pixels outside of the frame are ignored.
+    pub fn set(&mut self, x: usize, y: usize, color: Color) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+}
+
+/// Destination of the frames produced by a [`Visualize`] solver.
+pub trait FrameSink {
+    fn frame(&mut self, frame: &Frame) -> std::io::Result<()>;
+
+    fn finish(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Solvers able to replay their simulation frame by frame.
+pub trait Visualize {
+    fn visualize(&self, sink: &mut dyn FrameSink) -> std::io::Result<()>;
+}
+
+/// Chooses the sink from the `--visualize` argument: `terminal`, a `.gif` file or a directory
+/// receiving one PPM image per frame.
+pub fn frame_sink(target: &str) -> std::io::Result<Box<dyn FrameSink>> {
+    Ok(if target == "terminal" {
+        Box::new(TerminalView::new(Duration::from_millis(50)))
+    } else if target.ends_with(".gif") {
+        Box::new(GifWriter::new(File::create(target)?, 5))
+    } else {
+        Box::new(PpmDirectory::new(target)?)
+    })
+}
+
+/// Animates frames in place using 24-bit ANSI colors, two pixels per character cell.
+pub struct TerminalView {
+    delay: Duration,
+}
+
+impl TerminalView {
+    pub fn new(delay: Duration) -> Self {
+        Self { delay }
+    }
+}
+
+impl FrameSink for TerminalView {
+    fn frame(&mut self, frame: &Frame) -> std::io::Result<()> {
+        let mut out = String::from("\x1b[H\x1b[2J");
+        for y in (0..frame.height).step_by(2) {
+            for x in 0..frame.width {
+                let [tr, tg, tb] = frame.get(x, y);
+                let [br, bg, bb] = if y + 1 < frame.height {
+                    frame.get(x, y + 1)
+                } else {
+                    BLACK
+                };
+                out.push_str(&format!(
+                    "\x1b[38;2;{tr};{tg};{tb}m\x1b[48;2;{br};{bg};{bb}m\u{2580}"
+                ));
+            }
+            out.push_str("\x1b[0m\n");
+        }
+        let mut stdout = stdout().lock();
+        stdout.write_all(out.as_bytes())?;
+        stdout.flush()?;
+        sleep(self.delay);
+        Ok(())
+    }
+}
+
+/// Writes every frame as `frame00000.ppm`, `frame00001.ppm`, ... in a directory.
+pub struct PpmDirectory {
+    directory: PathBuf,
+    count: usize,
+}
+
+impl PpmDirectory {
+    pub fn new(directory: &str) -> std::io::Result<Self> {
+        create_dir_all(directory)?;
+        Ok(Self {
+            directory: PathBuf::from(directory),
+            count: 0,
+        })
+    }
+}
+
+impl FrameSink for PpmDirectory {
+    fn frame(&mut self, frame: &Frame) -> std::io::Result<()> {
+        let path = self.directory.join(format!("frame{:05}.ppm", self.count));
+        let mut file = BufWriter::new(File::create(path)?);
+        write!(file, "P6\n{} {}\n255\n", frame.width, frame.height)?;
+        file.write_all(&frame.pixels.concat())?;
+        self.count += 1;
+        file.flush()
+    }
+}
+
+/// Animated GIF encoder, each frame carrying its own palette so frames are streamed to the
+/// output instead of kept in memory. Frames with more than 256 colors are reduced to 3-3-2 bits.
+pub struct GifWriter<W: Write> {
+    out: BufWriter<W>,
+    delay: u16,
+    size: Option<(u16, u16)>,
+}
+
+impl<W: Write> GifWriter<W> {
+    /// `delay` is the time between frames in hundredths of a second.
+    pub fn new(out: W, delay: u16) -> Self {
+        Self {
+            out: BufWriter::new(out),
+            delay,
+            size: None,
+        }
+    }
+
+    fn header(&mut self, width: u16, height: u16) -> std::io::Result<()> {
+        self.out.write_all(b"GIF89a")?;
+        self.out.write_all(&width.to_le_bytes())?;
+        self.out.write_all(&height.to_le_bytes())?;
+        // no global color table, the background and aspect ratio are unused
+        self.out.write_all(&[0, 0, 0])?;
+        // loop forever
+        self.out
+            .write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")
+    }
+}
+
+impl<W: Write> FrameSink for GifWriter<W> {
+    fn frame(&mut self, frame: &Frame) -> std::io::Result<()> {
+        let (width, height) = (frame.width as u16, frame.height as u16);
+        match self.size {
+            None => {
+                self.header(width, height)?;
+                self.size = Some((width, height));
+            }
+            Some(size) if size != (width, height) => {
+                return Err(std::io::Error::other("frames must all have the same size"));
+            }
+            _ => {}
+        }
+        let (palette, indexes) = palettize(&frame.pixels);
+
+        self.out.write_all(&[0x21, 0xf9, 4, 0])?;
+        self.out.write_all(&self.delay.to_le_bytes())?;
+        self.out.write_all(&[0, 0])?;
+
+        self.out.write_all(&[0x2c, 0, 0, 0, 0])?;
+        self.out.write_all(&width.to_le_bytes())?;
+        self.out.write_all(&height.to_le_bytes())?;
+        // local color table of 256 entries
+        self.out.write_all(&[0x87])?;
+        for i in 0..256 {
+            self.out.write_all(palette.get(i).unwrap_or(&BLACK))?;
+        }
+        self.out.write_all(&[8])?;
+        for block in lzw_encode(&indexes).chunks(255) {
+            self.out.write_all(&[block.len() as u8])?;
+            self.out.write_all(block)?;
+        }
+        self.out.write_all(&[0])
+    }
+
+    fn finish(&mut self) -> std::io::Result<()> {
+        self.out.write_all(&[0x3b])?;
+        self.out.flush()
+    }
+}
+
+fn palettize(pixels: &[Color]) -> (Vec<Color>, Vec<u8>) {
+    let mut palette: Vec<Color> = Vec::new();
+    let mut lookup: HashMap<Color, u8> = HashMap::new();
+    for &pixel in pixels {
+        if let Entry::Vacant(entry) = lookup.entry(pixel) {
+            if palette.len() == 256 {
+                return three_three_two(pixels);
+            }
+            entry.insert(palette.len() as u8);
+            palette.push(pixel);
+        }
+    }
+    (palette, pixels.iter().map(|p| lookup[p]).collect())
+}
+
+/// Fixed palette keeping the 3 high bits of red and green and 2 of blue.
+fn three_three_two(pixels: &[Color]) -> (Vec<Color>, Vec<u8>) {
+    let palette = (0..=255u8)
+        .map(|i| [i & 0xe0, (i << 3) & 0xe0, (i << 6) & 0xc0])
+        .collect();
+    let indexes = pixels
+        .iter()
+        .map(|&[r, g, b]| (r & 0xe0) | ((g & 0xe0) >> 3) | (b >> 6))
+        .collect();
+    (palette, indexes)
+}
+
+/// Variable width LZW compression of 8 bit indexes as used by GIF.
+fn lzw_encode(indexes: &[u8]) -> Vec<u8> {
+    const CLEAR: u16 = 256;
+    const END: u16 = 257;
+    let mut bytes = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    let mut width = 9;
+    let mut emit = |code: u16, width: u32, bytes: &mut Vec<u8>| {
+        buffer |= (code as u32) << bits;
+        bits += width;
+        while bits >= 8 {
+            bytes.push(buffer as u8);
+            buffer >>= 8;
+            bits -= 8;
+        }
+    };
+
+    let mut dictionary: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next_code = END + 1;
+    emit(CLEAR, width, &mut bytes);
+    let mut current: Option<u16> = None;
+    for &index in indexes {
+        let Some(prefix) = current else {
+            current = Some(index as u16);
+            continue;
+        };
+        if let Some(&code) = dictionary.get(&(prefix, index)) {
+            current = Some(code);
+            continue;
+        }
+        emit(prefix, width, &mut bytes);
+        // like most decoders, stop one code short of the 12 bit limit before clearing
+        if next_code == 4095 {
+            emit(CLEAR, width, &mut bytes);
+            dictionary.clear();
+            next_code = END + 1;
+            width = 9;
+        } else {
+            dictionary.insert((prefix, index), next_code);
+            if next_code == 1 << width {
+                width += 1;
+            }
+            next_code += 1;
+        }
+        current = Some(index as u16);
+    }
+    if let Some(prefix) = current {
+        emit(prefix, width, &mut bytes);
+    }
+    emit(END, width, &mut bytes);
+    if bits > 0 {
+        bytes.push(buffer as u8);
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Straightforward GIF LZW decoder to check the encoder against.
+    fn lzw_decode(bytes: &[u8]) -> Vec<u8> {
+        let (mut position, mut width) = (0usize, 9usize);
+        let mut read = |width: usize| {
+            let code = (0..width)
+                .map(|i| ((bytes[(position + i) / 8] >> ((position + i) % 8)) & 1) as u16)
+                .enumerate()
+                .fold(0, |acc, (i, b)| acc | (b << i));
+            position += width;
+            code
+        };
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut output = Vec::new();
+        let mut previous: Option<Vec<u8>> = None;
+        loop {
+            let code = read(width) as usize;
+            if code == 256 {
+                table = (0..=255u8)
+                    .map(|i| vec![i])
+                    .chain([vec![], vec![]])
+                    .collect();
+                width = 9;
+                previous = None;
+                continue;
+            }
+            if code == 257 {
+                return output;
+            }
+            let entry = match (table.get(code), &previous) {
+                (Some(e), _) => e.clone(),
+                (None, Some(p)) => [p.clone(), vec![p[0]]].concat(),
+                (None, None) => panic!("invalid code"),
+            };
+            output.extend(&entry);
+            if let Some(p) = previous {
+                table.push([p, vec![entry[0]]].concat());
+                if table.len() == 1 << width && width < 12 {
+                    width += 1;
+                }
+            }
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trips() {
+        let indexes = (0..20000u32)
+            .map(|i| ((i * i / 7) % 13 + (i / 1000) * 17) as u8)
+            .collect::<Vec<_>>();
+        assert_eq!(lzw_decode(&lzw_encode(&indexes)), indexes);
+        assert_eq!(lzw_decode(&lzw_encode(&[])), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn writes_gif_and_ppm_frames() {
+        let frame = Frame::from_fn(3, 2, |x, y| if x == y { RED } else { BLUE });
+        let mut gif = GifWriter::new(Vec::new(), 10);
+        gif.frame(&frame).unwrap();
+        gif.frame(&frame).unwrap();
+        assert!(gif.frame(&Frame::new(1, 1, BLACK)).is_err());
+        gif.finish().unwrap();
+        let bytes = gif.out.into_inner().unwrap();
+        assert_eq!(&bytes[..10], b"GIF89a\x03\x00\x02\x00");
+        assert_eq!(bytes.last(), Some(&0x3b));
+
+        let directory = std::env::temp_dir().join(format!("advent-ppm-{}", std::process::id()));
+        let mut ppm = PpmDirectory::new(directory.to_str().unwrap()).unwrap();
+        ppm.frame(&frame).unwrap();
+        let written = std::fs::read(directory.join("frame00000.ppm")).unwrap();
+        assert_eq!(&written[..11], b"P6\n3 2\n255\n");
+        assert_eq!(&written[11..14], &RED);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -1,5 +1,6 @@
+use crate::common::search::{BranchAndBound, SearchStats, Strategy, branch_and_bound};
 use crate::solver::AdventSolver;
-use std::collections::HashSet;
+use std::cmp::Reverse;
 
 pub struct Advent2015Day22Solver {}
 
@@ -12,54 +13,56 @@ impl Advent2015Day22Solver {
 
 impl AdventSolver for Advent2015Day22Solver {
     fn solve_part1(&self) -> usize {
-        let mut states: HashSet<State> = HashSet::new();
-        states.insert(State {
-            player: 50,
-            mana: 500,
-            boss: 58,
-            damage: 9,
-            shield: 0,
-            poison: 0,
-            recharge: 0,
-            spent: 0,
-            hard: false,
-        });
-        while states.iter().any(|s| !s.is_over()) {
-            states = states.iter().flat_map(|s| s.next_states()).collect();
-        }
-        states
-            .iter()
-            .filter(|s| s.boss == 0)
-            .map(|s| s.spent)
-            .min()
+        least_mana_to_win(State::new(50, 500, 58, 9, false))
+            .0
             .unwrap()
     }
 
     fn solve_part2(&self) -> usize {
-        let mut states: HashSet<State> = HashSet::new();
-        states.insert(State {
-            player: 50,
-            mana: 500,
-            boss: 58,
-            damage: 9,
-            shield: 0,
-            poison: 0,
-            recharge: 0,
-            spent: 0,
-            hard: true,
-        });
-        while states.iter().any(|s| !s.is_over()) {
-            states = states.iter().flat_map(|s| s.next_states()).collect();
-        }
-        states
-            .iter()
-            .filter(|s| s.boss == 0)
-            .map(|s| s.spent)
-            .min()
+        least_mana_to_win(State::new(50, 500, 58, 9, true))
+            .0
             .unwrap()
     }
 }
 
+fn least_mana_to_win(start: State) -> (Option<usize>, SearchStats) {
+    let outcome = branch_and_bound(&Duel, start, Strategy::BestFirst);
+    (outcome.value().map(|Reverse(spent)| spent), outcome.stats())
+}
+
+struct Duel;
+
+impl BranchAndBound for Duel {
+    type Node = State;
+    type Key = State;
+    type Value = Reverse<usize>;
+
+    fn successors(&self, state: &State) -> Vec<State> {
+        state.next_states()
+    }
+
+    fn value(&self, state: &State) -> Option<Reverse<usize>> {
+        (state.boss == 0).then_some(Reverse(state.spent))
+    }
+
+    /// A boss still standing needs at least another magic missile.
+    fn bound(&self, state: &State) -> Reverse<usize> {
+        if state.boss == 0 {
+            Reverse(state.spent)
+        } else {
+            Reverse(state.spent + 53)
+        }
+    }
+
+    fn key(&self, state: &State) -> State {
+        State { spent: 0, ..*state }
+    }
+
+    fn dominates(&self, state: &State, other: &State) -> bool {
+        state.spent <= other.spent
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
 struct State {
     player: usize,
@@ -150,6 +153,20 @@ impl Spell for RechargeSpell {
 }
 
 impl State {
+    fn new(player: usize, mana: usize, boss: usize, damage: usize, hard: bool) -> Self {
+        Self {
+            player,
+            mana,
+            shield: 0,
+            recharge: 0,
+            boss,
+            damage,
+            poison: 0,
+            spent: 0,
+            hard,
+        }
+    }
+
     fn is_over(&self) -> bool {
         self.player == 0 || self.boss == 0
     }
@@ -198,7 +215,7 @@ impl State {
 
     fn next_states(&self) -> Vec<State> {
         if self.is_over() {
-            return vec![*self];
+            return vec![];
         }
 
         let spells: Vec<Box<dyn Spell>> = vec![
@@ -227,3 +244,32 @@ impl State {
 fn clamped_minus(before: usize, reduction: usize) -> usize {
     before.saturating_sub(reduction)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn spends_least_mana_to_win() {
+        assert_eq!(
+            least_mana_to_win(State::new(10, 250, 13, 8, false)).0,
+            Some(226)
+        );
+        assert_eq!(
+            least_mana_to_win(State::new(10, 250, 14, 8, false)).0,
+            Some(641)
+        );
+    }
+
+    #[test]
+    fn prunes_duels_that_cannot_beat_the_best() {
+        let (_, stats) = least_mana_to_win(State::new(10, 250, 14, 8, false));
+        assert_eq!(
+            stats,
+            SearchStats {
+                expanded: 35,
+                pruned: 13
+            }
+        );
+    }
+}
@@ -1,9 +1,11 @@
-use std::collections::{HashMap, HashSet};
+use std::cmp::Reverse;
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 
 use itertools::Itertools;
 use regex::Regex;
 
+use crate::common::search::{BranchAndBound, SearchStats, Strategy, branch_and_bound};
 use crate::solver::AdventSolver;
 
 const BOTTOM: u8 = 1;
@@ -51,34 +53,79 @@ impl Advent2016Day11Solver {
     }
 }
 
+impl Advent2016Day11Solver {
+    fn with_extra_pairs(&self) -> State {
+        let mut state = State::init(self);
+        state.generators.extend([BOTTOM, BOTTOM]);
+        state.microchips.extend([BOTTOM, BOTTOM]);
+        state
+    }
+}
+
 impl AdventSolver for Advent2016Day11Solver {
     fn solve_part1(&self) -> usize {
-        let mut states: HashSet<State> = HashSet::new();
-        states.insert(State::init(self));
-        let mut count = 0;
-        while !states.iter().any(|s| s.is_completed()) {
-            states = states.iter().flat_map(|s| s.next_states()).collect();
-            count += 1;
-        }
-        count
+        fewest_steps(State::init(self)).0
     }
 
     fn solve_part2(&self) -> usize {
-        let mut states: HashSet<State> = HashSet::new();
-        let mut state = State::init(self);
-        state.generators.extend([BOTTOM, BOTTOM]);
-        state.microchips.extend([BOTTOM, BOTTOM]);
-        states.insert(state);
-        let mut count = 0;
-        while !states.iter().any(|s| s.is_completed()) {
-            states = states.iter().flat_map(|s| s.next_states()).collect();
-            count += 1;
-        }
-        count
+        fewest_steps(self.with_extra_pairs()).0
     }
 }
 
-#[derive(Eq, Hash, PartialEq)]
+fn fewest_steps(state: State) -> (usize, SearchStats) {
+    let outcome = branch_and_bound(&Facility, (state, 0), Strategy::BestFirst);
+    (
+        outcome.value().map(|Reverse(steps)| steps).unwrap(),
+        outcome.stats(),
+    )
+}
+
+struct Facility;
+
+impl BranchAndBound for Facility {
+    type Node = (State, usize);
+    type Key = (u8, Vec<(u8, u8)>);
+    type Value = Reverse<usize>;
+
+    fn successors(&self, (state, steps): &(State, usize)) -> Vec<(State, usize)> {
+        state.next_states().map(|s| (s, steps + 1)).collect()
+    }
+
+    fn value(&self, (state, steps): &(State, usize)) -> Option<Reverse<usize>> {
+        state.is_completed().then_some(Reverse(*steps))
+    }
+
+    /// The elevator carries at most two items up a floor per step.
+    fn bound(&self, (state, steps): &(State, usize)) -> Reverse<usize> {
+        let floors: usize = state
+            .generators
+            .iter()
+            .chain(&state.microchips)
+            .map(|f| (TOP - f) as usize)
+            .sum();
+        Reverse(steps + floors.div_ceil(2))
+    }
+
+    /// Pairs are interchangeable, only the floors they are on matter.
+    fn key(&self, (state, _): &(State, usize)) -> (u8, Vec<(u8, u8)>) {
+        (
+            state.elevator,
+            state
+                .generators
+                .iter()
+                .copied()
+                .zip(state.microchips.iter().copied())
+                .sorted()
+                .collect(),
+        )
+    }
+
+    fn dominates(&self, (_, steps): &(State, usize), (_, other): &(State, usize)) -> bool {
+        steps <= other
+    }
+}
+
+#[derive(Clone, Eq, Hash, PartialEq)]
 struct State {
     generators: Vec<u8>,
     microchips: Vec<u8>,
@@ -182,3 +229,27 @@ fn modify_vec(initial: &[u8], index: usize, modification: fn(u8) -> u8) -> Vec<u
     clone[index] = modification(clone[index]);
     clone
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn moves_everything_to_the_top_floor() {
+        let solver = Advent2016Day11Solver::new(
+            "\
+The first floor contains a hydrogen-compatible microchip and a lithium-compatible microchip.
+The second floor contains a hydrogen generator.
+The third floor contains a lithium generator.
+The fourth floor contains nothing relevant.",
+        );
+        assert_eq!(solver.solve_part1(), 11);
+        assert_eq!(
+            fewest_steps(State::init(&solver)).1,
+            SearchStats {
+                expanded: 132,
+                pruned: 300
+            }
+        );
+    }
+}
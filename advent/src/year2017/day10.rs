@@ -34,6 +34,12 @@ pub struct KnotHash {
     skip: usize,
 }
 
+impl Default for KnotHash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl KnotHash {
     pub fn new() -> Self {
         Self {
@@ -1,10 +1,9 @@
 use std::collections::VecDeque;
-use std::ops::AddAssign;
 
 use itertools::Itertools;
-use num_traits::abs;
 use regex::{Match, Regex};
 
+use crate::common::vec3::Vec3;
 use crate::solver::AdventSolver;
 
 pub struct Advent2017Day20Solver {
@@ -22,9 +21,9 @@ impl Advent2017Day20Solver {
                 .map(|(i, c)| {
                     Particle::new(
                         i,
-                        Vector::new(to_isize(c.get(1)), to_isize(c.get(2)), to_isize(c.get(3))),
-                        Vector::new(to_isize(c.get(4)), to_isize(c.get(5)), to_isize(c.get(6))),
-                        Vector::new(to_isize(c.get(7)), to_isize(c.get(8)), to_isize(c.get(9))),
+                        Vec3::new(to_i64(c.get(1)), to_i64(c.get(2)), to_i64(c.get(3))),
+                        Vec3::new(to_i64(c.get(4)), to_i64(c.get(5)), to_i64(c.get(6))),
+                        Vec3::new(to_i64(c.get(7)), to_i64(c.get(8)), to_i64(c.get(9))),
                     )
                 })
                 .collect(),
@@ -76,13 +75,13 @@ impl AdventSolver for Advent2017Day20Solver {
 #[derive(Clone)]
 struct Particle {
     index: usize,
-    position: Vector,
-    velocity: Vector,
-    acceleration: Vector,
+    position: Vec3,
+    velocity: Vec3,
+    acceleration: Vec3,
 }
 
 impl Particle {
-    fn new(index: usize, position: Vector, velocity: Vector, acceleration: Vector) -> Self {
+    fn new(index: usize, position: Vec3, velocity: Vec3, acceleration: Vec3) -> Self {
         Self {
             index,
             position,
@@ -92,12 +91,12 @@ impl Particle {
     }
 
     fn update(&mut self) {
-        self.velocity += &self.acceleration;
-        self.position += &self.velocity;
+        self.velocity += self.acceleration;
+        self.position += self.velocity;
     }
 
-    fn distance_to_zero(&self) -> isize {
-        self.position.distance_to_zero()
+    fn distance_to_zero(&self) -> i64 {
+        self.position.manhattan()
     }
 
     fn collides_with(&self, other: &Self) -> bool {
@@ -105,31 +104,6 @@ impl Particle {
     }
 }
 
-#[derive(Clone, Eq, PartialEq)]
-struct Vector {
-    x: isize,
-    y: isize,
-    z: isize,
-}
-
-impl Vector {
-    fn new(x: isize, y: isize, z: isize) -> Self {
-        Self { x, y, z }
-    }
-
-    fn distance_to_zero(&self) -> isize {
-        abs(self.x) + abs(self.y) + abs(self.z)
-    }
-}
-
-impl AddAssign<&Vector> for Vector {
-    fn add_assign(&mut self, rhs: &Vector) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-        self.z += rhs.z;
-    }
-}
-
-fn to_isize(value: Option<Match>) -> isize {
+fn to_i64(value: Option<Match>) -> i64 {
     value.unwrap().as_str().parse().unwrap()
 }
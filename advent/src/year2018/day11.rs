@@ -1,3 +1,4 @@
+use crate::common::parallel;
 use crate::solver::AdventSolver;
 
 pub struct Advent2018Day11Solver {
@@ -24,75 +25,62 @@ impl AdventSolver for Advent2018Day11Solver {
     }
 }
 
+/// Summed-area table, `sums[y][x]` holds the power of every cell above and left of `(x, y)`,
+/// so any square total takes four lookups.
 struct Grid {
-    grid: Vec<Vec<isize>>,
+    sums: Vec<Vec<isize>>,
 }
 
 impl Grid {
     fn new(serial_number: usize) -> Self {
-        let grid = (0..300)
-            .map(|y| {
-                (0..300)
-                    .map(|x| {
-                        let rack_id = x + 11;
-                        let mut power: isize = rack_id * (y + 1);
-                        power += serial_number as isize;
-                        power *= rack_id;
-                        power /= 100;
-                        power %= 10;
-                        power - 5
-                    })
-                    .collect()
-            })
-            .collect();
-        Self { grid }
+        let mut sums = vec![vec![0; 301]; 301];
+        for y in 0..300 {
+            for x in 0..300 {
+                let rack_id = x as isize + 11;
+                let mut power: isize = rack_id * (y as isize + 1);
+                power += serial_number as isize;
+                power *= rack_id;
+                power /= 100;
+                power %= 10;
+                sums[y + 1][x + 1] = power - 5 + sums[y][x + 1] + sums[y + 1][x] - sums[y][x];
+            }
+        }
+        Self { sums }
     }
 
+    /// Squares of every size are independent, the first largest one wins ties.
     fn largest_any(&self) -> (((usize, usize), isize), usize) {
-        let mut sum_grid: Vec<Vec<isize>> = self.grid.clone();
-        let mut best: (((usize, usize), isize), usize) = (
-            sum_grid
-                .iter()
-                .enumerate()
-                .map(|(y, row)| (y, row.iter().enumerate().max_by_key(|(_, s)| *s).unwrap()))
-                .map(|(y, (x, s))| ((x, y), *s))
-                .max_by_key(|(_, s)| *s)
-                .unwrap(),
-            1,
-        );
-        for size in 1..300 {
-            for y in 0..300 - size {
-                for x in 0..300 - size {
-                    for i in 0..size {
-                        sum_grid[y][x] += self.grid[y + size][x + i] + self.grid[y + i][x + size];
-                    }
-                    sum_grid[y][x] += self.grid[y + size][x + size];
-                    if sum_grid[y][x] > best.0 .1 {
-                        best = (((x, y), sum_grid[y][x]), size + 1);
-                    }
-                }
-            }
-        }
-        best
+        let sizes: Vec<usize> = (1..=300).collect();
+        parallel::map(&sizes, |&size| (self.largest(size), size))
+            .into_iter()
+            .reduce(|best, s| if s.0.1 > best.0.1 { s } else { best })
+            .unwrap()
     }
 
     fn largest(&self, size: usize) -> ((usize, usize), isize) {
-        (0..300 - size)
-            .flat_map(|y| (0..300 - size).map(move |x| self.total((x, y), size)))
-            .max_by_key(|(_, t)| *t)
+        (0..=300 - size)
+            .flat_map(|y| (0..=300 - size).map(move |x| self.total((x, y), size)))
+            .reduce(|best, t| if t.1 > best.1 { t } else { best })
             .unwrap()
     }
 
-    fn total(&self, position: (usize, usize), size: usize) -> ((usize, usize), isize) {
+    fn total(&self, (x, y): (usize, usize), size: usize) -> ((usize, usize), isize) {
+        let s = &self.sums;
         (
-            position,
-            (0..size)
-                .map(|y| {
-                    (0..size)
-                        .map(|x| self.grid[y + position.1][x + position.0])
-                        .sum::<isize>()
-                })
-                .sum(),
+            (x, y),
+            s[y + size][x + size] - s[y][x + size] - s[y + size][x] + s[y][x],
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn finds_largest_squares() {
+        assert_eq!(Grid::new(18).largest(3), ((32, 44), 29));
+        assert_eq!(Grid::new(42).largest(3), ((20, 60), 30));
+        assert_eq!(Grid::new(18).largest_any(), (((89, 268), 113), 16));
+    }
+}
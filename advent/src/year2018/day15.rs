@@ -5,6 +5,7 @@ use std::fmt::{Debug, Display, Formatter};
 use itertools::Itertools;
 
 use crate::solver::AdventSolver;
+use crate::visualize::{BLACK, Frame, FrameSink, GRAY, GREEN, RED, Visualize};
 
 pub struct Advent2018Day15Solver {
     map: Map,
@@ -61,6 +62,24 @@ impl AdventSolver for Advent2018Day15Solver {
         }
         winning_outcome.unwrap()
     }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
+}
+
+impl Visualize for Advent2018Day15Solver {
+    fn visualize(&self, sink: &mut dyn FrameSink) -> std::io::Result<()> {
+        let mut map = self.map.clone();
+        sink.frame(&map.frame())?;
+        let mut result = Ok(());
+        map.run_with(|m| {
+            if result.is_ok() {
+                result = sink.frame(&m.frame());
+            }
+        });
+        result
+    }
 }
 
 #[derive(Clone)]
@@ -96,9 +115,28 @@ impl Map {
     }
 
     fn run(&mut self) {
+        self.run_with(|_| {});
+    }
+
+    /// Runs the combat, calling `on_round` after every round.
+    fn run_with(&mut self, mut on_round: impl FnMut(&Self)) {
         while !self.iterate() && !self.is_done() {
             self.rounds += 1;
+            on_round(self);
+        }
+    }
+
+    /// Walls in gray, units fading to black as they lose health.
+    fn frame(&self) -> Frame {
+        let mut frame = Frame::from_fn(self.grid[0].len(), self.grid.len(), |x, y| {
+            if self.grid[y][x] { BLACK } else { GRAY }
+        });
+        for unit in self.units.iter().filter(|u| !u.is_dead()) {
+            let color = if unit.is_elf { GREEN } else { RED };
+            let shade = |c: u8| (c as usize * (unit.health + 55) / 255) as u8;
+            frame.set(unit.pos.x, unit.pos.y, color.map(shade));
         }
+        frame
     }
 
     fn is_done(&self) -> bool {
@@ -1,4 +1,5 @@
 use crate::solver::AdventSolver;
+use crate::visualize::{BLACK, BLUE, BROWN, Frame, FrameSink, LIGHT_BLUE, Visualize};
 use itertools::Itertools;
 use regex::{Match, Regex};
 use std::collections::HashSet;
@@ -48,6 +49,26 @@ impl AdventSolver for Advent2018Day17Solver {
     fn solve_part2(&self) -> usize {
         self.map.count_resting()
     }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
+}
+
+impl Visualize for Advent2018Day17Solver {
+    fn visualize(&self, sink: &mut dyn FrameSink) -> std::io::Result<()> {
+        const STEPS_PER_FRAME: usize = 200;
+        let mut map = Map::new(self.map.clay());
+        let mut steps = 0;
+        while !map.is_done() {
+            if steps % STEPS_PER_FRAME == 0 {
+                sink.frame(&map.frame())?;
+            }
+            map.step();
+            steps += 1;
+        }
+        sink.frame(&map.frame())
+    }
 }
 
 type Pos = (usize, usize);
@@ -171,6 +192,34 @@ impl Map {
         self.flowing.is_empty()
     }
 
+    fn clay(&self) -> HashSet<Pos> {
+        (0..self.map.len())
+            .flat_map(|y| (0..self.map[y].len()).map(move |x| (y, x)))
+            .filter(|&(y, x)| matches!(self.map[y][x], State::Clay))
+            .collect()
+    }
+
+    /// Crops the map to the columns where water can flow.
+    fn frame(&self) -> Frame {
+        let min_x = self
+            .map
+            .iter()
+            .filter_map(|row| row.iter().position(|s| matches!(s, State::Clay)))
+            .min()
+            .unwrap_or(1)
+            - 1;
+        Frame::from_fn(
+            self.map[0].len() - min_x,
+            self.max_y + 2,
+            |x, y| match self.map[y][x + min_x] {
+                State::Clay => BROWN,
+                State::Rest => BLUE,
+                State::Flow => LIGHT_BLUE,
+                State::Empty => BLACK,
+            },
+        )
+    }
+
     fn count_water(&self) -> usize {
         (self.min_y..=self.max_y)
             .map(|y| {
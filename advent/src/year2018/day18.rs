@@ -1,4 +1,5 @@
 use crate::solver::AdventSolver;
+use crate::visualize::{BLACK, BROWN, Frame, FrameSink, GREEN, Visualize};
 use State::{Edge, Lumberyard, Open, Trees};
 
 pub struct Advent2018Day18Solver {
@@ -44,6 +45,26 @@ impl AdventSolver for Advent2018Day18Solver {
         }
         find_map(&self.map).resource_value()
     }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
+}
+
+impl Visualize for Advent2018Day18Solver {
+    /// Plays the minutes until the area starts repeating itself.
+    fn visualize(&self, sink: &mut dyn FrameSink) -> std::io::Result<()> {
+        let mut seen = vec![self.map.clone()];
+        sink.frame(&self.map.frame())?;
+        loop {
+            let next = seen.last().unwrap().step();
+            sink.frame(&next.frame())?;
+            if seen.contains(&next) {
+                return Ok(());
+            }
+            seen.push(next);
+        }
+    }
 }
 
 #[derive(Clone, Eq, PartialEq)]
@@ -127,6 +148,18 @@ impl Map {
             .sum()
     }
 
+    fn frame(&self) -> Frame {
+        Frame::from_fn(
+            self.map[0].len() - 2,
+            self.map.len() - 2,
+            |x, y| match self.map[y + 1][x + 1] {
+                Trees => GREEN,
+                Lumberyard => BROWN,
+                Open | Edge => BLACK,
+            },
+        )
+    }
+
     fn step(&self) -> Self {
         let mut next_map = vec![];
         for y in 0..self.map.len() {
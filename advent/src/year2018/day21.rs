@@ -1,3 +1,4 @@
+use crate::cancel;
 use crate::solver::AdventSolver;
 use num_traits::ToPrimitive;
 use std::fmt::{Debug, Formatter};
@@ -37,7 +38,7 @@ impl AdventSolver for Advent2018Day21Solver {
         // todo slow (43s)
         let mut computer = Computer::new(self.ip);
         let mut values = Vec::with_capacity(20000);
-        while !values.contains(&computer.registers[5]) {
+        while !values.contains(&computer.registers[5]) && !cancel::cancelled() {
             values.push(computer.registers[5]);
             computer.execute_program_until(&self.program, stop_condition);
         }
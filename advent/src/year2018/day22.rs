@@ -1,3 +1,4 @@
+use crate::cancel;
 use crate::solver::AdventSolver;
 use itertools::Itertools;
 use regex::Regex;
@@ -108,6 +109,9 @@ impl Map {
         queue.push_back((0, 1));
         let mut best_target = usize::MAX;
         while let Some(pos) = queue.pop_front() {
+            if cancel::cancelled() {
+                break;
+            }
             let region = self.region(pos);
             let mut positions_around = vec![(pos.0 + 1, pos.1), (pos.0, pos.1 + 1)];
             if pos.0 > 0 {
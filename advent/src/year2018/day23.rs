@@ -1,5 +1,8 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::common::cuboid::{Cuboid, rotate_manhattan};
 use crate::solver::AdventSolver;
-use itertools::Itertools;
 use regex::{Match, Regex};
 
 pub struct Advent2018Day23Solver {
@@ -43,25 +46,65 @@ impl AdventSolver for Advent2018Day23Solver {
     }
 
     fn solve_part2(&self) -> usize {
-        self.nanobots
-            .iter()
-            .flat_map(|n| {
-                let d = n.pos.distance_from_origin();
-                vec![(i64::max(d - n.radius, 0), 1), (d + n.radius + 1, -1)]
-            })
-            .sorted_by_key(|(d, _)| *d)
-            .fold((0, 0, 0), |(b, c, m), (d, e)| {
-                let c2 = c + e;
-                if c2 > m {
-                    (d, c2, c2)
-                } else {
-                    (b, c2, m)
-                }
-            })
-            .0 as usize
+        // Every range is a box in the rotated space (a, b, c, d) of rotate_manhattan, where the
+        // distance to the origin is the largest absolute coordinate. Points are pinned down by
+        // (b, c, d) alone, a being their sum, so best-first subdivision runs over boxes of
+        // (b, c, d) lifted into 4 dimensions. Lifting only ever widens a box, which keeps the
+        // number of ranges it meets an upper bound and its distance a lower bound, and both are
+        // exact once a box is a single point.
+        let ranges = self.nanobots.iter().map(Nanobot::range).collect::<Vec<_>>();
+        let bounds = Cuboid::new(
+            std::array::from_fn(|i| ranges.iter().map(|r| r.min[i + 1]).min().unwrap()),
+            std::array::from_fn(|i| ranges.iter().map(|r| r.max[i + 1]).max().unwrap()),
+        );
+        let entry = |region: Cuboid<3>| {
+            let lifted = lift(&region);
+            let in_range = if region.volume() == 1 && !is_lattice_point(&region.min) {
+                0
+            } else {
+                ranges.iter().filter(|r| r.intersects(&lifted)).count()
+            };
+            (
+                in_range,
+                Reverse(distance_from_origin(&lifted)),
+                Reverse(region.volume()),
+                region,
+            )
+        };
+        let mut queue = BinaryHeap::new();
+        queue.push(entry(bounds));
+        while let Some((_, Reverse(distance), Reverse(volume), region)) = queue.pop() {
+            if volume == 1 {
+                return distance as usize;
+            }
+            queue.extend(region.split().into_iter().map(entry));
+        }
+        unreachable!("search space is never empty")
     }
 }
 
+/// Rotated box of the points whose last three rotated coordinates lie in `region`.
+fn lift(region: &Cuboid<3>) -> Cuboid<4> {
+    let (min, max) = (region.min, region.max);
+    Cuboid::new(
+        [min[0] + min[1] + min[2], min[0], min[1], min[2]],
+        [max[0] + max[1] + max[2], max[0], max[1], max[2]],
+    )
+}
+
+/// Only (b, c, d) of equal parity rotate back to integer points, x being (b + c) / 2.
+fn is_lattice_point(bcd: &[i64; 3]) -> bool {
+    (bcd[0] - bcd[1]) % 2 == 0 && (bcd[0] - bcd[2]) % 2 == 0
+}
+
+/// Smallest Manhattan distance to the origin of a point in a rotated box.
+fn distance_from_origin(rotated: &Cuboid<4>) -> u64 {
+    (0..4)
+        .map(|i| Cuboid::new([rotated.min[i]], [rotated.max[i]]).manhattan_distance(&[0]))
+        .max()
+        .unwrap()
+}
+
 #[derive(Debug, Clone, Copy)]
 struct Pos(i64, i64, i64);
 
@@ -70,8 +113,8 @@ impl Pos {
         self.0 + self.1 + self.2
     }
 
-    fn distance_to(&self, other: &Pos) -> i64 {
-        (self.0.abs_diff(other.0) + self.1.abs_diff(other.1) + self.2.abs_diff(other.2)) as i64
+    fn coords(&self) -> [i64; 3] {
+        [self.0, self.1, self.2]
     }
 }
 
@@ -82,8 +125,12 @@ struct Nanobot {
 }
 
 impl Nanobot {
+    fn range(&self) -> Cuboid<4> {
+        Cuboid::from_manhattan_ball(self.pos.coords(), self.radius)
+    }
+
     fn is_in_range_of(&self, other: &Nanobot) -> bool {
-        other.pos.distance_to(&self.pos) <= other.radius
+        other.range().contains(&rotate_manhattan(self.pos.coords()))
     }
 }
 
@@ -1,3 +1,4 @@
+use crate::cancel;
 use crate::solver::AdventSolver;
 use std::collections::VecDeque;
 
@@ -30,6 +31,8 @@ impl AdventSolver for Advent2019Day09Solver {
     }
 }
 
+const CANCEL_POLL_INTERVAL: usize = 1 << 16;
+
 struct Computer {
     program: Vec<Value>,
     pointer: usize,
@@ -71,7 +74,14 @@ impl Computer {
 
     fn run(&mut self) {
         self.state = ComputerState::Running;
+        let mut executed = 0usize;
         while matches!(self.state, ComputerState::Running) {
+            // the token sits behind a thread local, too slow to read on every instruction
+            if executed.is_multiple_of(CANCEL_POLL_INTERVAL) && cancel::cancelled() {
+                self.state = ComputerState::Halted;
+                break;
+            }
+            executed += 1;
             self.pointer += self.execute_operation();
         }
     }
@@ -1,9 +1,7 @@
+use crate::common::vec3::Vec3;
 use crate::solver::AdventSolver;
-use num_traits::Inv;
 use regex::Regex;
-use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::ops::AddAssign;
 use num_integer::Integer;
 
 pub struct Advent2019Day12Solver {
@@ -54,14 +52,14 @@ impl AdventSolver for Advent2019Day12Solver {
 
 #[derive(Debug, Clone)]
 struct MoonMemory {
-    coord_fn: fn (Coord) -> i64,
+    coord_fn: fn (Vec3) -> i64,
     start: Option<usize>,
     repeat: Option<usize>,
     seen: HashMap<Vec<(i64,i64)>, usize>,
 }
 
 impl MoonMemory {
-    fn new(coord_fn: fn (Coord) -> i64) -> Self {
+    fn new(coord_fn: fn (Vec3) -> i64) -> Self {
         Self {
             coord_fn,
             start: None,
@@ -96,14 +94,8 @@ struct MoonSystem {
 }
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 struct Moon {
-    pos: Coord,
-    vel: Coord,
-}
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Coord {
-    x: i64,
-    y: i64,
-    z: i64,
+    pos: Vec3,
+    vel: Vec3,
 }
 
 impl MoonSystem {
@@ -129,7 +121,7 @@ impl MoonSystem {
             for m2 in m1+1..self.moons.len() {
                 let gravity_effect = self.moons[m1].calculate_gravity(&self.moons[m2]);
                 self.moons[m1].apply_gravity(gravity_effect);
-                self.moons[m2].apply_gravity(gravity_effect.inv());
+                self.moons[m2].apply_gravity(-gravity_effect);
             }
         }
     }
@@ -149,25 +141,16 @@ impl MoonSystem {
 impl Moon {
     fn new(x: i64, y: i64, z: i64) -> Self {
         Self {
-            pos: Coord { x, y, z },
-            vel: Coord { x: 0, y: 0, z: 0 },
+            pos: Vec3::new(x, y, z),
+            vel: Vec3::zero(),
         }
     }
 
-    fn calculate_gravity(&self, rhs: &Self) -> Coord {
-        const TO_EFFECT: fn(Ordering) -> i64 = |o| match o {
-            Ordering::Equal => 0,
-            Ordering::Greater => -1,
-            Ordering::Less => 1,
-        };
-        Coord {
-            x: TO_EFFECT(self.pos.x.cmp(&rhs.pos.x)),
-            y: TO_EFFECT(self.pos.y.cmp(&rhs.pos.y)),
-            z: TO_EFFECT(self.pos.z.cmp(&rhs.pos.z)),
-        }
+    fn calculate_gravity(&self, rhs: &Self) -> Vec3 {
+        (rhs.pos - self.pos).signum()
     }
 
-    fn apply_gravity(&mut self, effect: Coord) {
+    fn apply_gravity(&mut self, effect: Vec3) {
         self.vel += effect;
     }
 
@@ -180,37 +163,11 @@ impl Moon {
     }
 
     fn potential_energy(&self) -> i64 {
-        self.pos.energy()
+        self.pos.manhattan()
     }
 
     fn kinetic_energy(&self) -> i64 {
-        self.vel.energy()
-    }
-}
-
-impl Coord {
-    fn energy(&self) -> i64 {
-        self.x.abs() + self.y.abs() + self.z.abs()
-    }
-}
-
-impl Inv for Coord {
-    type Output = Self;
-
-    fn inv(self) -> Self::Output {
-        Self {
-            x: -self.x,
-            y: -self.y,
-            z: -self.z,
-        }
-    }
-}
-
-impl AddAssign for Coord {
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-        self.z += rhs.z;
+        self.vel.manhattan()
     }
 }
 
@@ -1,134 +1,481 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+use itertools::Itertools;
+
 use crate::solver::AdventSolver;
 
 pub struct Advent2021Day16Solver {
-    bits: Bits,
+    packet: Packet,
 }
 
 impl Advent2021Day16Solver {
     pub fn new(input: &str) -> Self {
         Self {
-            bits: Bits::new(input.chars().flat_map(to_bits).collect()),
+            packet: Packet::decode(input).unwrap(),
         }
     }
 }
 
 impl AdventSolver for Advent2021Day16Solver {
     fn solve_part1(&self) -> usize {
-        let mut bits = self.bits.clone();
-        Packet::new(&mut bits).version_sum()
+        self.packet.version_sum()
     }
 
     fn solve_part2(&self) -> usize {
-        let mut bits = self.bits.clone();
-        Packet::new(&mut bits).expression_value()
+        self.packet.value()
     }
 }
 
-struct Packet {
-    version: usize,
-    type_id: usize,
-    value: usize,
-    sub_packets: Vec<Packet>,
+const LITERAL: u8 = 4;
+const MAX_BIT_LENGTH: usize = (1 << 15) - 1;
+const MAX_SUB_PACKETS: usize = (1 << 11) - 1;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Packet {
+    pub version: u8,
+    pub content: Content,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Content {
+    Literal(usize),
+    Operator {
+        operator: Operator,
+        length_type: LengthType,
+        sub_packets: Vec<Packet>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+/// How an operator packet announces its sub-packets, by their total length in bits or by
+/// their count.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthType {
+    Bits,
+    Count,
+}
+
+pub struct DecodeError {
+    pub offset: usize,
+    pub kind: DecodeErrorKind,
+}
+
+pub enum DecodeErrorKind {
+    InvalidHexDigit(char),
+    UnexpectedEnd,
+    LiteralTooLarge,
+    WrongOperandCount(Operator, usize),
+    LengthMismatch { expected: usize, actual: usize },
+    NonZeroPadding,
+}
+
+pub enum EncodeError {
+    VersionTooLarge(u8),
+    WrongOperandCount(Operator, usize),
+    TooManyBits(usize),
+    TooManySubPackets(usize),
+}
+
+impl Operator {
+    const ALL: [Operator; 7] = [
+        Operator::Sum,
+        Operator::Product,
+        Operator::Minimum,
+        Operator::Maximum,
+        Operator::GreaterThan,
+        Operator::LessThan,
+        Operator::EqualTo,
+    ];
+
+    fn from_type_id(type_id: u8) -> Self {
+        match type_id {
+            0..=3 => Self::ALL[type_id as usize],
+            5..=7 => Self::ALL[type_id as usize - 1],
+            _ => panic!("type id {type_id} is a literal"),
+        }
+    }
+
+    fn type_id(&self) -> u8 {
+        match self {
+            Operator::Sum => 0,
+            Operator::Product => 1,
+            Operator::Minimum => 2,
+            Operator::Maximum => 3,
+            Operator::GreaterThan => 5,
+            Operator::LessThan => 6,
+            Operator::EqualTo => 7,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Operator::Sum => "sum",
+            Operator::Product => "product",
+            Operator::Minimum => "min",
+            Operator::Maximum => "max",
+            Operator::GreaterThan => "gt",
+            Operator::LessThan => "lt",
+            Operator::EqualTo => "eq",
+        }
+    }
+
+    fn accepts(&self, operands: usize) -> bool {
+        match self {
+            Operator::GreaterThan | Operator::LessThan | Operator::EqualTo => operands == 2,
+            _ => operands > 0,
+        }
+    }
+
+    /// Sums and products wrap around rather than overflow.
+    fn apply(&self, mut operands: impl Iterator<Item = usize>) -> usize {
+        match self {
+            Operator::Sum => operands.fold(0, usize::wrapping_add),
+            Operator::Product => operands.fold(1, usize::wrapping_mul),
+            Operator::Minimum => operands.min().unwrap(),
+            Operator::Maximum => operands.max().unwrap(),
+            comparison => {
+                let (a, b) = (operands.next().unwrap(), operands.next().unwrap());
+                let result = match comparison {
+                    Operator::GreaterThan => a > b,
+                    Operator::LessThan => a < b,
+                    _ => a == b,
+                };
+                result as usize
+            }
+        }
+    }
 }
 
 impl Packet {
-    fn new(bits: &mut Bits) -> Packet {
-        let v = bits.read_n(3);
-        let t = bits.read_n(3);
-        if t == 4 {
-            Packet {
-                version: v,
-                type_id: t,
-                value: bits.read_number(),
-                sub_packets: Vec::new(),
+    pub fn literal(version: u8, value: usize) -> Self {
+        Self {
+            version,
+            content: Content::Literal(value),
+        }
+    }
+
+    pub fn operator(
+        version: u8,
+        operator: Operator,
+        length_type: LengthType,
+        sub_packets: Vec<Packet>,
+    ) -> Self {
+        Self {
+            version,
+            content: Content::Operator {
+                operator,
+                length_type,
+                sub_packets,
+            },
+        }
+    }
+
+    /// Decodes a whole transmission, which may only be followed by zero padding.
+    pub fn decode(hex: &str) -> Result<Self, DecodeError> {
+        let mut bits = Bits::from_hex(hex.trim())?;
+        let packet = Self::read(&mut bits)?;
+        match bits.bits[bits.current..].iter().position(|&b| b == 1) {
+            Some(p) => Err(DecodeError {
+                offset: bits.current + p,
+                kind: DecodeErrorKind::NonZeroPadding,
+            }),
+            None => Ok(packet),
+        }
+    }
+
+    /// Encodes the packet as hexadecimal, padded with zeros to a whole number of bytes.
+    pub fn encode(&self) -> Result<String, EncodeError> {
+        let mut bits = Vec::new();
+        self.write(&mut bits)?;
+        bits.resize(bits.len().next_multiple_of(8), 0);
+        Ok(bits
+            .chunks(4)
+            .map(|c| {
+                let digit = c.iter().fold(0, |acc, &b| acc * 2 + b as u32);
+                char::from_digit(digit, 16).unwrap().to_ascii_uppercase()
+            })
+            .collect())
+    }
+
+    fn read(bits: &mut Bits) -> Result<Self, DecodeError> {
+        let version = bits.read_n(3)? as u8;
+        let type_id = bits.read_n(3)? as u8;
+        if type_id == LITERAL {
+            return Ok(Self::literal(version, bits.read_number()?));
+        }
+        let operator = Operator::from_type_id(type_id);
+        let start = bits.current;
+        let mut sub_packets = Vec::new();
+        let length_type = if bits.read_n(1)? == 0 {
+            let length = bits.read_n(15)?;
+            let before = bits.current;
+            while bits.current < before + length {
+                sub_packets.push(Self::read(bits)?);
+            }
+            if bits.current != before + length {
+                return Err(DecodeError {
+                    offset: before,
+                    kind: DecodeErrorKind::LengthMismatch {
+                        expected: length,
+                        actual: bits.current - before,
+                    },
+                });
             }
+            LengthType::Bits
         } else {
-            let mut sp = Vec::new();
-            let l = bits.read_n(1);
-            match l {
-                0 => {
-                    let length = bits.read_n(15);
-                    let before = bits.current;
-                    while bits.current != before + length {
-                        sp.push(Packet::new(bits));
-                    }
+            let count = bits.read_n(11)?;
+            for _ in 0..count {
+                sub_packets.push(Self::read(bits)?);
+            }
+            LengthType::Count
+        };
+        if !operator.accepts(sub_packets.len()) {
+            return Err(DecodeError {
+                offset: start,
+                kind: DecodeErrorKind::WrongOperandCount(operator, sub_packets.len()),
+            });
+        }
+        Ok(Self::operator(version, operator, length_type, sub_packets))
+    }
+
+    fn write(&self, bits: &mut Vec<u8>) -> Result<(), EncodeError> {
+        if self.version > 7 {
+            return Err(EncodeError::VersionTooLarge(self.version));
+        }
+        write_n(bits, self.version as usize, 3);
+        match &self.content {
+            Content::Literal(value) => {
+                write_n(bits, LITERAL as usize, 3);
+                let groups = (usize::BITS - value.leading_zeros()).div_ceil(4).max(1);
+                for g in (0..groups).rev() {
+                    bits.push((g != 0) as u8);
+                    write_n(bits, (value >> (4 * g)) & 0xF, 4);
+                }
+            }
+            Content::Operator {
+                operator,
+                length_type,
+                sub_packets,
+            } => {
+                if !operator.accepts(sub_packets.len()) {
+                    return Err(EncodeError::WrongOperandCount(*operator, sub_packets.len()));
                 }
-                1 => {
-                    let mut count = bits.read_n(11);
-                    while count > 0 {
-                        sp.push(Packet::new(bits));
-                        count -= 1;
+                write_n(bits, operator.type_id() as usize, 3);
+                let mut content = Vec::new();
+                for packet in sub_packets {
+                    packet.write(&mut content)?;
+                }
+                match length_type {
+                    LengthType::Bits if content.len() > MAX_BIT_LENGTH => {
+                        return Err(EncodeError::TooManyBits(content.len()));
+                    }
+                    LengthType::Bits => {
+                        bits.push(0);
+                        write_n(bits, content.len(), 15);
+                    }
+                    LengthType::Count if sub_packets.len() > MAX_SUB_PACKETS => {
+                        return Err(EncodeError::TooManySubPackets(sub_packets.len()));
+                    }
+                    LengthType::Count => {
+                        bits.push(1);
+                        write_n(bits, sub_packets.len(), 11);
                     }
                 }
-                _ => panic!("unknown length type id"),
+                bits.extend(content);
             }
-            Packet {
-                version: v,
-                type_id: t,
-                value: usize::MAX,
-                sub_packets: sp,
+        }
+        Ok(())
+    }
+
+    pub fn version_sum(&self) -> usize {
+        self.version as usize
+            + match &self.content {
+                Content::Literal(_) => 0,
+                Content::Operator { sub_packets, .. } => {
+                    sub_packets.iter().map(Packet::version_sum).sum()
+                }
             }
+    }
+
+    pub fn value(&self) -> usize {
+        match &self.content {
+            Content::Literal(value) => *value,
+            Content::Operator {
+                operator,
+                sub_packets,
+                ..
+            } => operator.apply(sub_packets.iter().map(Packet::value)),
         }
     }
 
-    fn version_sum(&self) -> usize {
-        if self.type_id == 4 {
-            return self.version;
+    fn fmt_indented(&self, f: &mut Formatter<'_>, indent: usize) -> std::fmt::Result {
+        match &self.content {
+            Content::Literal(value) => write!(f, "(literal v{} {value})", self.version),
+            Content::Operator {
+                operator,
+                length_type,
+                sub_packets,
+            } => {
+                let length_type = match length_type {
+                    LengthType::Bits => "bits",
+                    LengthType::Count => "count",
+                };
+                write!(
+                    f,
+                    "({} v{} {length_type} ={}",
+                    operator.name(),
+                    self.version,
+                    self.value()
+                )?;
+                for packet in sub_packets {
+                    write!(f, "\n{}", "  ".repeat(indent + 1))?;
+                    packet.fmt_indented(f, indent + 1)?;
+                }
+                write!(f, ")")
+            }
         }
+    }
+}
 
-        self.version
-            + self
-                .sub_packets
-                .iter()
-                .fold(0, |acc, sp| acc + sp.version_sum())
+/// S-expression with the value of every operator after `=`, which [`Packet::from_str`] reads
+/// back, ignoring the values.
+impl Display for Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.fmt_indented(f, 0)
     }
+}
 
-    fn expression_value(&self) -> usize {
-        match self.type_id {
-            0 => self
-                .sub_packets
-                .iter()
-                .fold(0, |acc, sp| acc + sp.expression_value()),
-            1 => self
-                .sub_packets
-                .iter()
-                .fold(1, |acc, sp| acc * sp.expression_value()),
-            2 => self
-                .sub_packets
-                .iter()
-                .map(|sp| sp.expression_value())
-                .min()
-                .unwrap(),
-            3 => self
-                .sub_packets
-                .iter()
-                .map(|sp| sp.expression_value())
-                .max()
-                .unwrap(),
-            4 => self.value,
-            5 => {
-                if self.sub_packets[0].expression_value() > self.sub_packets[1].expression_value() {
-                    1
-                } else {
-                    0
-                }
+/// Reads `(literal v3 10)` or `(sum v1 count (literal v0 1) ...)`, versions default to 0 and
+/// length types to bits.
+impl FromStr for Packet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let spaced = s.replace('(', " ( ").replace(')', " ) ");
+        let mut tokens = spaced.split_whitespace().peekable();
+        let packet = parse_packet(&mut tokens)?;
+        match tokens.next() {
+            Some(token) => Err(format!("unexpected `{token}` after the outermost packet")),
+            None => Ok(packet),
+        }
+    }
+}
+
+fn parse_packet<'a>(
+    tokens: &mut std::iter::Peekable<impl Iterator<Item = &'a str>>,
+) -> Result<Packet, String> {
+    match tokens.next() {
+        Some("(") => {}
+        Some(token) => return Err(format!("expected `(`, found `{token}`")),
+        None => return Err("expected `(`, found the end".to_string()),
+    }
+    let name = tokens.next().ok_or("missing packet type")?;
+    let mut version = 0;
+    let mut length_type = LengthType::Bits;
+    let mut literal = None;
+    let mut sub_packets = Vec::new();
+    loop {
+        match tokens.peek().copied() {
+            None => return Err(format!("unclosed `{name}`")),
+            Some(")") => {
+                tokens.next();
+                break;
             }
-            6 => {
-                if self.sub_packets[0].expression_value() < self.sub_packets[1].expression_value() {
-                    1
+            Some("(") => sub_packets.push(parse_packet(tokens)?),
+            Some(token) => {
+                tokens.next();
+                if let Some(v) = token.strip_prefix('v') {
+                    version = v
+                        .parse()
+                        .map_err(|_| format!("invalid version `{token}`"))?;
+                } else if token == "bits" {
+                    length_type = LengthType::Bits;
+                } else if token == "count" {
+                    length_type = LengthType::Count;
+                } else if token.starts_with('=') {
+                    // evaluated value printed for reading only
                 } else {
-                    0
+                    literal = Some(
+                        token
+                            .parse()
+                            .map_err(|_| format!("invalid value `{token}`"))?,
+                    );
                 }
             }
-            7 => {
-                if self.sub_packets[0].expression_value() == self.sub_packets[1].expression_value()
-                {
-                    1
-                } else {
-                    0
-                }
+        }
+    }
+    if name == "literal" {
+        return match (literal, sub_packets.is_empty()) {
+            (Some(value), true) => Ok(Packet::literal(version, value)),
+            _ => Err("a literal holds exactly one value".to_string()),
+        };
+    }
+    let operator = Operator::ALL
+        .into_iter()
+        .find(|o| o.name() == name)
+        .ok_or(format!("unknown packet type `{name}`"))?;
+    if literal.is_some() {
+        return Err(format!("`{name}` cannot hold a value"));
+    }
+    Ok(Packet::operator(
+        version,
+        operator,
+        length_type,
+        sub_packets,
+    ))
+}
+
+impl Debug for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bit {}: ", self.offset)?;
+        match &self.kind {
+            DecodeErrorKind::InvalidHexDigit(c) => write!(f, "invalid hexadecimal digit {c:?}"),
+            DecodeErrorKind::UnexpectedEnd => {
+                write!(f, "transmission ends in the middle of a packet")
+            }
+            DecodeErrorKind::LiteralTooLarge => {
+                write!(f, "literal does not fit in {} bits", usize::BITS)
+            }
+            DecodeErrorKind::WrongOperandCount(operator, count) => {
+                write!(f, "`{}` cannot take {count} sub-packets", operator.name())
+            }
+            DecodeErrorKind::LengthMismatch { expected, actual } => {
+                write!(f, "sub-packets announced {expected} bits but used {actual}")
+            }
+            DecodeErrorKind::NonZeroPadding => write!(f, "padding after the packet is not zero"),
+        }
+    }
+}
+
+impl Debug for EncodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncodeError::VersionTooLarge(version) => {
+                write!(f, "version {version} does not fit in 3 bits")
+            }
+            EncodeError::WrongOperandCount(operator, count) => {
+                write!(f, "`{}` cannot take {count} sub-packets", operator.name())
+            }
+            EncodeError::TooManyBits(bits) => {
+                write!(
+                    f,
+                    "{bits} bits of sub-packets do not fit in 15 bits, use count"
+                )
+            }
+            EncodeError::TooManySubPackets(count) => {
+                write!(f, "{count} sub-packets do not fit in 11 bits, use bits")
             }
-            _ => panic!("unknown type_id"),
         }
     }
 }
@@ -140,55 +487,196 @@ struct Bits {
 }
 
 impl Bits {
-    fn new(bits: Vec<u8>) -> Bits {
-        Bits {
-            bits: bits.to_vec(),
-            current: 0,
+    fn from_hex(hex: &str) -> Result<Bits, DecodeError> {
+        let bits = hex
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                c.to_digit(16).ok_or(DecodeError {
+                    offset: 4 * i,
+                    kind: DecodeErrorKind::InvalidHexDigit(c),
+                })
+            })
+            .map_ok(|d| (0..4).rev().map(move |b| (d >> b) as u8 & 1))
+            .flatten_ok()
+            .collect::<Result<_, _>>()?;
+        Ok(Bits { bits, current: 0 })
+    }
+
+    fn read_n(&mut self, size: usize) -> Result<usize, DecodeError> {
+        if self.current + size > self.bits.len() {
+            return Err(DecodeError {
+                offset: self.current,
+                kind: DecodeErrorKind::UnexpectedEnd,
+            });
         }
+        let number = self.bits[self.current..self.current + size]
+            .iter()
+            .fold(0, |acc, &b| acc * 2 + b as usize);
+        self.current += size;
+        Ok(number)
     }
 
-    fn read_n(&mut self, mut size: usize) -> usize {
+    fn read_number(&mut self) -> Result<usize, DecodeError> {
+        let start = self.current;
         let mut number: usize = 0;
-        while size != 0 {
-            number = number * 2 + self.bits[self.current] as usize;
-            self.current += 1;
-            size -= 1;
+        loop {
+            let more = self.read_n(1)?;
+            if number >> (usize::BITS - 4) != 0 {
+                return Err(DecodeError {
+                    offset: start,
+                    kind: DecodeErrorKind::LiteralTooLarge,
+                });
+            }
+            number = number * 16 + self.read_n(4)?;
+            if more == 0 {
+                return Ok(number);
+            }
         }
-        number
     }
+}
 
-    fn read_number(&mut self) -> usize {
-        let mut number: usize = 0;
-        while self.bits[self.current] == 1 {
-            self.current += 1;
-            number *= 16;
-            number += self.read_n(4);
-        }
-        self.current += 1;
-        number *= 16;
-        number += self.read_n(4);
-        number
-    }
-}
-
-fn to_bits(c: char) -> [u8; 4] {
-    match c {
-        '0' => [0, 0, 0, 0],
-        '1' => [0, 0, 0, 1],
-        '2' => [0, 0, 1, 0],
-        '3' => [0, 0, 1, 1],
-        '4' => [0, 1, 0, 0],
-        '5' => [0, 1, 0, 1],
-        '6' => [0, 1, 1, 0],
-        '7' => [0, 1, 1, 1],
-        '8' => [1, 0, 0, 0],
-        '9' => [1, 0, 0, 1],
-        'A' => [1, 0, 1, 0],
-        'B' => [1, 0, 1, 1],
-        'C' => [1, 1, 0, 0],
-        'D' => [1, 1, 0, 1],
-        'E' => [1, 1, 1, 0],
-        'F' => [1, 1, 1, 1],
-        _ => panic!("unexpected char"),
+fn write_n(bits: &mut Vec<u8>, number: usize, size: usize) {
+    bits.extend((0..size).rev().map(|b| (number >> b) as u8 & 1));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decodes_examples() {
+        assert_eq!(Packet::decode("D2FE28").unwrap(), Packet::literal(6, 2021));
+        assert_eq!(
+            Packet::decode("38006F45291200").unwrap(),
+            Packet::operator(
+                1,
+                Operator::LessThan,
+                LengthType::Bits,
+                vec![Packet::literal(6, 10), Packet::literal(2, 20)]
+            )
+        );
+        for (hex, version_sum) in [
+            ("8A004A801A8002F478", 16),
+            ("620080001611562C8802118E34", 12),
+            ("C0015000016115A2E0802F182340", 23),
+            ("A0016C880162017C3686B18A3D4780", 31),
+        ] {
+            assert_eq!(Packet::decode(hex).unwrap().version_sum(), version_sum);
+        }
+        for (hex, value) in [
+            ("C200B40A82", 3),
+            ("04005AC33890", 54),
+            ("880086C3E88112", 7),
+            ("CE00C43D881120", 9),
+            ("D8005AC2A8F0", 1),
+            ("F600BC2D8F", 0),
+            ("9C005AC2F8F0", 0),
+            ("9C0141080250320F1802104A08", 1),
+        ] {
+            assert_eq!(Packet::decode(hex).unwrap().value(), value);
+            assert_eq!(Packet::decode(hex).unwrap().encode().unwrap(), hex);
+        }
+    }
+
+    #[test]
+    fn reports_decode_errors_with_offsets() {
+        let offset = |hex: &str| Packet::decode(hex).unwrap_err().offset;
+        assert_eq!(offset("D2FG28"), 12);
+        assert_eq!(offset("D2FE"), 16);
+        assert_eq!(offset("D2FE29"), 23);
+        let single = Packet::operator(
+            0,
+            Operator::Sum,
+            LengthType::Count,
+            vec![Packet::literal(0, 1)],
+        );
+        let mut hex = single.encode().unwrap();
+        // turns the sum into a comparison
+        hex.replace_range(0..2, "16");
+        let error = Packet::decode(&hex).unwrap_err();
+        assert_eq!(error.offset, 6);
+        assert!(matches!(
+            error.kind,
+            DecodeErrorKind::WrongOperandCount(Operator::GreaterThan, 1)
+        ));
+    }
+
+    #[test]
+    fn prints_and_parses_s_expressions() {
+        let packet = Packet::decode("9C0141080250320F1802104A08").unwrap();
+        let printed = packet.to_string();
+        assert_eq!(
+            printed,
+            "\
+(eq v4 bits =1
+  (sum v2 count =4
+    (literal v2 1)
+    (literal v4 3))
+  (product v6 count =4
+    (literal v0 2)
+    (literal v2 2)))"
+        );
+        assert_eq!(printed.parse::<Packet>().unwrap(), packet);
+        assert_eq!(
+            "(max (literal 5) (literal v7 9))"
+                .parse::<Packet>()
+                .unwrap()
+                .encode()
+                .unwrap(),
+            "0C005842F890"
+        );
+        assert!("(sum (literal 1)".parse::<Packet>().is_err());
+        assert!("(pow (literal 1))".parse::<Packet>().is_err());
+        assert!(matches!(
+            "(lt (literal 1))".parse::<Packet>().unwrap().encode(),
+            Err(EncodeError::WrongOperandCount(Operator::LessThan, 1))
+        ));
+    }
+
+    /// Small xorshift generator, enough to build random trees.
+    struct Random(u64);
+
+    impl Random {
+        fn below(&mut self, n: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % n as u64) as usize
+        }
+
+        fn packet(&mut self, depth: usize) -> Packet {
+            let version = self.below(8) as u8;
+            if depth == 0 || self.below(3) == 0 {
+                let value = match self.below(3) {
+                    0 => self.below(16),
+                    1 => self.below(1 << 20),
+                    _ => self.0 as usize,
+                };
+                return Packet::literal(version, value);
+            }
+            let operator = Operator::ALL[self.below(7)];
+            let operands = if operator.accepts(1) {
+                1 + self.below(4)
+            } else {
+                2
+            };
+            let length_type = [LengthType::Bits, LengthType::Count][self.below(2)];
+            let sub_packets = (0..operands).map(|_| self.packet(depth - 1)).collect();
+            Packet::operator(version, operator, length_type, sub_packets)
+        }
+    }
+
+    #[test]
+    fn round_trips_random_packets() {
+        let mut random = Random(0x2021_0016);
+        for _ in 0..500 {
+            let packet = random.packet(4);
+            let hex = packet.encode().unwrap();
+            let decoded = Packet::decode(&hex).unwrap();
+            assert_eq!(decoded, packet);
+            assert_eq!(decoded.encode().unwrap(), hex);
+            assert_eq!(packet.to_string().parse::<Packet>().unwrap(), packet);
+        }
     }
 }
@@ -1,5 +1,6 @@
 use regex::Regex;
 
+use crate::common::cuboid::{Cuboid, CuboidSet};
 use crate::solver::AdventSolver;
 
 pub struct Advent2021Day22Solver {
@@ -17,15 +18,11 @@ impl Advent2021Day22Solver {
                 .lines()
                 .map(|l| re.captures(l).unwrap())
                 .map(|c| {
-                    Step::new(
-                        c.get(1).unwrap().as_str(),
-                        c.get(2).unwrap().as_str().parse().unwrap(),
-                        c.get(3).unwrap().as_str().parse().unwrap(),
-                        c.get(4).unwrap().as_str().parse().unwrap(),
-                        c.get(5).unwrap().as_str().parse().unwrap(),
-                        c.get(6).unwrap().as_str().parse().unwrap(),
-                        c.get(7).unwrap().as_str().parse().unwrap(),
-                    )
+                    let get = |i: usize| c.get(i).unwrap().as_str().parse().unwrap();
+                    Step {
+                        on: c.get(1).unwrap().as_str() == "on",
+                        region: Cuboid::new([get(2), get(4), get(6)], [get(3), get(5), get(7)]),
+                    }
                 })
                 .collect(),
         }
@@ -34,186 +31,52 @@ impl Advent2021Day22Solver {
 
 impl AdventSolver for Advent2021Day22Solver {
     fn solve_part1(&self) -> usize {
-        self.steps
-            .iter()
-            .filter(|s| s.is_initialization())
-            .fold(Vec::new(), |acc, s| execute_regions(s, &acc))
-            .iter()
-            .map(|r| r.size())
-            .sum()
+        execute(self.steps.iter().filter(|s| s.is_initialization())).volume() as usize
     }
 
     fn solve_part2(&self) -> usize {
-        self.steps
-            .iter()
-            .fold(Vec::new(), |acc, s| execute_regions(s, &acc))
-            .iter()
-            .map(|r| r.size())
-            .sum()
+        execute(self.steps.iter()).volume() as usize
     }
 }
 
-fn execute_regions(step: &Step, regions: &[Region]) -> Vec<Region> {
-    let step_region = step.region();
-    let mut new_regions: Vec<Region> = regions
-        .iter()
-        .flat_map(|r| r.split_x(step.x))
-        .flat_map(|r| r.split_y(step.y))
-        .flat_map(|r| r.split_z(step.z))
-        .filter(|r| !r.is_within(&step_region))
-        .collect();
-    if step.on {
-        new_regions.push(step_region);
-    }
-    new_regions
-}
-
-struct Region {
-    x: (i32, i32),
-    y: (i32, i32),
-    z: (i32, i32),
-}
-
-impl Region {
-    fn new(x: (i32, i32), y: (i32, i32), z: (i32, i32)) -> Self {
-        Self { x, y, z }
-    }
-
-    fn copy(&self) -> Self {
-        Self {
-            x: self.x,
-            y: self.y,
-            z: self.z,
-        }
-    }
-
-    fn split_x(&self, other: (i32, i32)) -> Vec<Self> {
-        if self.x.1 < other.0 || self.x.0 > other.1 {
-            vec![self.copy()]
-        } else if self.x.0 < other.0 {
-            if self.x.1 <= other.1 {
-                vec![
-                    Self::new((self.x.0, other.0 - 1), self.y, self.z),
-                    Self::new((other.0, self.x.1), self.y, self.z),
-                ]
-            } else {
-                vec![
-                    Self::new((self.x.0, other.0 - 1), self.y, self.z),
-                    Self::new((other.0, other.1), self.y, self.z),
-                    Self::new((other.1 + 1, self.x.1), self.y, self.z),
-                ]
-            }
-        } else if self.x.1 <= other.1 {
-            vec![self.copy()]
-        } else {
-            vec![
-                Self::new((self.x.0, other.1), self.y, self.z),
-                Self::new((other.1 + 1, self.x.1), self.y, self.z),
-            ]
-        }
-    }
-
-    fn split_y(&self, other: (i32, i32)) -> Vec<Self> {
-        if self.y.1 < other.0 || self.y.0 > other.1 {
-            vec![self.copy()]
-        } else if self.y.0 < other.0 {
-            if self.y.1 <= other.1 {
-                vec![
-                    Self::new(self.x, (self.y.0, other.0 - 1), self.z),
-                    Self::new(self.x, (other.0, self.y.1), self.z),
-                ]
-            } else {
-                vec![
-                    Self::new(self.x, (self.y.0, other.0 - 1), self.z),
-                    Self::new(self.x, (other.0, other.1), self.z),
-                    Self::new(self.x, (other.1 + 1, self.y.1), self.z),
-                ]
-            }
-        } else if self.y.1 <= other.1 {
-            vec![self.copy()]
+fn execute<'a>(steps: impl Iterator<Item = &'a Step>) -> CuboidSet<3> {
+    let mut reactor = CuboidSet::new();
+    for step in steps {
+        if step.on {
+            reactor.insert(step.region);
         } else {
-            vec![
-                Self::new(self.x, (self.y.0, other.1), self.z),
-                Self::new(self.x, (other.1 + 1, self.y.1), self.z),
-            ]
+            reactor.remove(&step.region);
         }
     }
-
-    fn split_z(&self, other: (i32, i32)) -> Vec<Self> {
-        if self.z.1 < other.0 || self.z.0 > other.1 {
-            vec![self.copy()]
-        } else if self.z.0 < other.0 {
-            if self.z.1 <= other.1 {
-                vec![
-                    Self::new(self.x, self.y, (self.z.0, other.0 - 1)),
-                    Self::new(self.x, self.y, (other.0, self.z.1)),
-                ]
-            } else {
-                vec![
-                    Self::new(self.x, self.y, (self.z.0, other.0 - 1)),
-                    Self::new(self.x, self.y, (other.0, other.1)),
-                    Self::new(self.x, self.y, (other.1 + 1, self.z.1)),
-                ]
-            }
-        } else if self.z.1 <= other.1 {
-            vec![self.copy()]
-        } else {
-            vec![
-                Self::new(self.x, self.y, (self.z.0, other.1)),
-                Self::new(self.x, self.y, (other.1 + 1, self.z.1)),
-            ]
-        }
-    }
-
-    fn is_within(&self, other: &Region) -> bool {
-        self.x.0 >= other.x.0
-            && self.x.1 <= other.x.1
-            && self.y.0 >= other.y.0
-            && self.y.1 <= other.y.1
-            && self.z.0 >= other.z.0
-            && self.z.1 <= other.z.1
-    }
-
-    fn size(&self) -> usize {
-        ((self.x.1 - self.x.0 + 1).unsigned_abs() as usize)
-            * ((self.y.1 - self.y.0 + 1).unsigned_abs() as usize)
-            * ((self.z.1 - self.z.0 + 1).unsigned_abs() as usize)
-    }
+    reactor
 }
 
 struct Step {
     on: bool,
-    x: (i32, i32),
-    y: (i32, i32),
-    z: (i32, i32),
+    region: Cuboid<3>,
 }
 
 impl Step {
-    fn new(on: &str, xs: i32, xe: i32, ys: i32, ye: i32, zs: i32, ze: i32) -> Self {
-        Self {
-            on: on == "on",
-            x: (xs, xe),
-            y: (ys, ye),
-            z: (zs, ze),
-        }
-    }
-
     fn is_initialization(&self) -> bool {
-        self.x.0 >= -50
-            && self.x.0 <= 50
-            && self.x.1 >= -50
-            && self.x.1 <= 50
-            && self.y.0 >= -50
-            && self.y.0 <= 50
-            && self.y.1 >= -50
-            && self.y.1 <= 50
-            && self.z.0 >= -50
-            && self.z.0 <= 50
-            && self.z.1 >= -50
-            && self.z.1 <= 50
+        Cuboid::new([-50; 3], [50; 3]).contains_cuboid(&self.region)
     }
+}
 
-    fn region(&self) -> Region {
-        Region::new(self.x, self.y, self.z)
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn counts_cubes_on_after_reboot() {
+        let solver = Advent2021Day22Solver::new(
+            "\
+on x=10..12,y=10..12,z=10..12
+on x=11..13,y=11..13,z=11..13
+off x=9..11,y=9..11,z=9..11
+on x=10..10,y=10..10,z=10..10
+on x=-54112..-39298,y=-85059..-49293,z=-27449..7877",
+        );
+        assert_eq!(solver.solve_part1(), 39);
+        assert_eq!(solver.solve_part2(), 39 + 14815 * 35767 * 35327);
     }
 }
@@ -1,4 +1,7 @@
+use std::ops::Range;
+
 use crate::solver::AdventSolver;
+use crate::visualize::{BLACK, BROWN, Frame, FrameSink, Visualize, YELLOW};
 
 #[derive(Debug, Clone)]
 struct Pos(usize, usize);
@@ -98,6 +101,16 @@ impl SandMap {
         }
     }
 
+    fn frame(&self, columns: &Range<usize>) -> Frame {
+        Frame::from_fn(columns.len(), self.sand_map.len(), |x, y| {
+            match self.sand_map[y][x + columns.start] {
+                '#' => BROWN,
+                'o' => YELLOW,
+                _ => BLACK,
+            }
+        })
+    }
+
     fn add_floor(&mut self) {
         let increase = 200;
         let max_col = self.sand_map[0].len() + increase;
@@ -161,4 +174,28 @@ impl AdventSolver for Advent2022Day14Solver {
         }
         sand_count
     }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
+}
+
+impl Visualize for Advent2022Day14Solver {
+    /// Pours the sand of part 2, the pile never spreads further than its height from the source.
+    fn visualize(&self, sink: &mut dyn FrameSink) -> std::io::Result<()> {
+        const GRAINS_PER_FRAME: usize = 100;
+        let mut sand_map = self.to_map();
+        sand_map.add_floor();
+        let height = sand_map.sand_map.len();
+        let columns =
+            500usize.saturating_sub(height)..(500 + height).min(sand_map.sand_map[0].len());
+        let mut grains = 0;
+        while sand_map.drop_sand() {
+            if grains % GRAINS_PER_FRAME == 0 {
+                sink.frame(&sand_map.frame(&columns))?;
+            }
+            grains += 1;
+        }
+        sink.frame(&sand_map.frame(&columns))
+    }
 }
@@ -1,10 +1,8 @@
-use std::collections::HashSet;
 use std::ops::RangeInclusive;
 
-use num_bigint::BigUint;
-use num_traits::identities::Zero;
 use regex::Regex;
 
+use crate::common::intervals::IntervalSet;
 use crate::solver::AdventSolver;
 
 type Pos = (isize, isize);
@@ -43,20 +41,6 @@ impl Sensor {
         }
     }
 
-    fn overlap(&self, y: isize, neg_map: &mut BigUint, pos_map: &mut BigUint) {
-        match self.overlap_range(y) {
-            None => {}
-            Some(range) => {
-                if range.start() < &0 && range.end() > &0 {
-                    (0..=-range.start()).for_each(|x| neg_map.set_bit(x as u64, true));
-                    (0..=*range.end()).for_each(|x| pos_map.set_bit(x as u64, true));
-                } else {
-                    range.for_each(|x| pos_map.set_bit(x as u64, true));
-                }
-            }
-        }
-    }
-
     fn is_in_border(&self, pos: &Pos) -> bool {
         dist(&self.position, pos) <= self.dist
     }
@@ -110,28 +94,16 @@ impl Advent2022Day15Solver {
 
 impl AdventSolver for Advent2022Day15Solver {
     fn solve_part1(&self) -> usize {
-        let mut neg_map = BigUint::zero();
-        let mut pos_map = BigUint::zero();
-        let mut beacons = HashSet::new();
-        for sensor in &self.sensors {
-            sensor.overlap(self.part1_y, &mut neg_map, &mut pos_map);
-            if sensor.beacon.1 == self.part1_y {
-                beacons.insert(sensor.beacon.0);
-            }
-        }
-        beacons.iter().for_each(|x| {
-            if x < &0 {
-                neg_map.set_bit(-x as u64, false)
-            } else {
-                pos_map.set_bit(*x as u64, false)
-            }
-        });
-        neg_map.count_ones() as usize + pos_map.count_ones() as usize
-            - if neg_map.bit(0) && pos_map.bit(0) {
-                1
-            } else {
-                0
-            }
+        let mut covered: IntervalSet<isize> = self
+            .sensors
+            .iter()
+            .filter_map(|s| s.overlap_range(self.part1_y))
+            .collect();
+        self.sensors
+            .iter()
+            .filter(|s| s.beacon.1 == self.part1_y)
+            .for_each(|s| covered.remove(s.beacon.0..=s.beacon.0));
+        covered.len() as usize
     }
 
     fn solve_part2(&self) -> usize {
@@ -1,9 +1,9 @@
-use std::collections::{HashMap, HashSet};
-use std::slice::Iter;
+use std::collections::{HashMap, VecDeque};
 
 use itertools::Itertools;
 use regex::Regex;
 
+use crate::common::search::{BranchAndBound, SearchStats, Strategy, branch_and_bound};
 use crate::solver::AdventSolver;
 
 struct Valve {
@@ -45,6 +45,7 @@ impl Advent2022Day16Solver {
             .iter()
             .filter(|(_, v)| v.flow_rate != 0)
             .map(|(n, _)| n.clone())
+            .sorted()
             .collect();
         Self {
             valves,
@@ -56,176 +57,195 @@ impl Advent2022Day16Solver {
         self.valves.get(valve).unwrap().flow_rate
     }
 
-    fn leads_to(&self, valve: &String) -> Iter<'_, String> {
-        self.valves.get(valve).unwrap().leads_to.iter()
-    }
-}
-
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
-struct State {
-    pressure: usize,
-    pressure_total: usize,
-    open_valves: Vec<String>,
-    position: String,
-    elephant: String,
-}
-
-impl State {
-    fn new() -> Self {
-        Self {
-            pressure: 0,
-            pressure_total: 0,
-            open_valves: vec![],
-            position: String::from("AA"),
-            elephant: String::from("AA"),
+    fn distances_from<'a>(&'a self, start: &'a String) -> HashMap<&'a String, usize> {
+        let mut distances = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(current) = queue.pop_front() {
+            let distance = distances[current];
+            for next in &self.valves.get(current).unwrap().leads_to {
+                if !distances.contains_key(next) {
+                    distances.insert(next, distance + 1);
+                    queue.push_back(next);
+                }
+            }
         }
+        distances
     }
 
-    fn do_nothing(&self) -> Self {
-        Self {
-            pressure: self.pressure,
-            pressure_total: self.pressure_total + self.pressure,
-            open_valves: self.open_valves.clone(),
-            position: self.position.clone(),
-            elephant: self.elephant.clone(),
+    /// Valves worth opening, the start valve last, with the travel times between them.
+    fn network(&self) -> ValveNetwork {
+        let start = String::from("AA");
+        let valves = self
+            .valves_with_flow_rate
+            .iter()
+            .chain([&start])
+            .collect_vec();
+        let distances = valves
+            .iter()
+            .map(|&from| {
+                let reachable = self.distances_from(from);
+                valves
+                    .iter()
+                    .map(|to| reachable.get(to).copied().unwrap_or(usize::MAX / 2))
+                    .collect()
+            })
+            .collect();
+        ValveNetwork {
+            flow_rates: self
+                .valves_with_flow_rate
+                .iter()
+                .map(|v| self.flow_rate(v))
+                .collect(),
+            distances,
         }
     }
 
-    fn is_done(&self, solver: &Advent2022Day16Solver) -> bool {
-        self.open_valves.len() == solver.valves_with_flow_rate.len()
+    fn most_pressure(&self, time: usize, actors: usize) -> (usize, SearchStats) {
+        let network = self.network();
+        let start = network.flow_rates.len();
+        let state = State {
+            actors: vec![(time, start); actors],
+            opened: 0,
+            released: 0,
+        };
+        let outcome = branch_and_bound(&network, state, Strategy::DepthFirst);
+        (outcome.value().unwrap_or(0), outcome.stats())
     }
+}
 
-    fn can_open(&self, solver: &Advent2022Day16Solver, valve: &String) -> bool {
-        !self.open_valves.contains(valve) && solver.valves_with_flow_rate.contains(valve)
-    }
+struct ValveNetwork {
+    flow_rates: Vec<usize>,
+    distances: Vec<Vec<usize>>,
+}
 
-    fn next_states_alone(&self, solver: &Advent2022Day16Solver) -> Vec<Self> {
-        let mut nexts = vec![];
-        let next = self.do_nothing();
-        nexts.push(next.clone());
-        if self.is_done(solver) {
-            return nexts;
-        }
-        if self.can_open(solver, &self.position) {
-            let mut tmp = next.clone();
-            tmp.open_valves.push(self.position.clone());
-            tmp.pressure += solver.flow_rate(&self.position);
-            nexts.push(tmp);
-        }
-        for np in solver.leads_to(&self.position) {
-            let mut tmp = next.clone();
-            tmp.position = np.clone();
-            nexts.push(tmp);
+/// Every actor is at a valve with some time left, the pressure released by opened valves is
+/// counted up to the end as soon as they are opened.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct State {
+    actors: Vec<(usize, usize)>,
+    opened: u64,
+    released: usize,
+}
+
+impl BranchAndBound for ValveNetwork {
+    type Node = State;
+    type Key = (Vec<(usize, usize)>, u64);
+    type Value = usize;
+
+    /// The actor with the most time left goes and opens another valve, or stops for good.
+    fn successors(&self, state: &State) -> Vec<State> {
+        let Some((index, &(time, position))) =
+            state.actors.iter().enumerate().max_by_key(|(_, a)| a.0)
+        else {
+            return vec![];
+        };
+        if time == 0 {
+            return vec![];
         }
-        nexts
+        let mut next = (0..self.flow_rates.len())
+            .filter(|v| state.opened & (1 << v) == 0)
+            .filter(|&v| self.distances[position][v] < time)
+            .map(|v| {
+                let left = time - self.distances[position][v] - 1;
+                let mut actors = state.actors.clone();
+                actors[index] = (left, v);
+                State {
+                    actors,
+                    opened: state.opened | (1 << v),
+                    released: state.released + left * self.flow_rates[v],
+                }
+            })
+            .collect_vec();
+        let mut stop = state.clone();
+        stop.actors[index].0 = 0;
+        next.push(stop);
+        next
     }
 
-    fn open_valve(&self, solver: &Advent2022Day16Solver, valve: &String) -> Self {
-        let mut tmp = self.clone();
-        tmp.open_valves.push(valve.clone());
-        tmp.pressure += solver.flow_rate(valve);
-        tmp
+    fn value(&self, state: &State) -> Option<usize> {
+        Some(state.released)
     }
 
-    fn move_position_to(&self, valve: &str) -> Self {
-        let mut tmp = self.clone();
-        tmp.position = valve.to_owned();
-        tmp
+    /// Every closed valve opened by whichever actor reaches it first.
+    fn bound(&self, state: &State) -> usize {
+        state.released
+            + (0..self.flow_rates.len())
+                .filter(|v| state.opened & (1 << v) == 0)
+                .map(|v| {
+                    state
+                        .actors
+                        .iter()
+                        .map(|&(time, position)| {
+                            time.saturating_sub(self.distances[position][v] + 1)
+                        })
+                        .max()
+                        .unwrap_or(0)
+                        * self.flow_rates[v]
+                })
+                .sum::<usize>()
     }
 
-    fn move_elephant_to(&self, valve: &str) -> Self {
-        let mut tmp = self.clone();
-        tmp.elephant = valve.to_owned();
-        tmp
+    fn key(&self, state: &State) -> (Vec<(usize, usize)>, u64) {
+        (
+            state.actors.iter().copied().sorted().collect(),
+            state.opened,
+        )
     }
 
-    fn next_states(&self, solver: &Advent2022Day16Solver) -> Vec<Self> {
-        if self.is_done(solver) {
-            return vec![self.do_nothing()];
-        }
-        let mut nexts = vec![];
-        // nothing + nothing
-        let next = self.do_nothing();
-        nexts.push(next.clone());
-        if self.can_open(solver, &self.position) {
-            // open + nothing
-            let tmp = next.open_valve(solver, &self.position);
-            nexts.push(tmp.clone());
-
-            // open + open
-            if self.position != self.elephant && self.can_open(solver, &self.elephant) {
-                nexts.push(tmp.open_valve(solver, &self.elephant));
-            }
-
-            // open + move
-            for ne in solver.leads_to(&self.elephant) {
-                nexts.push(tmp.move_elephant_to(ne));
-            }
-        }
-        if self.can_open(solver, &self.elephant) {
-            // nothing + open
-            let tmp = next.open_valve(solver, &self.elephant);
-            nexts.push(tmp.clone());
-
-            // move + open
-            for np in solver.leads_to(&self.position) {
-                nexts.push(tmp.move_position_to(np));
-            }
-        }
-        // move + nothing
-        for np in solver.leads_to(&self.position) {
-            let tmp = next.move_position_to(np);
-            nexts.push(tmp.clone());
-
-            // move + move
-            for ne in solver.leads_to(&self.elephant) {
-                nexts.push(tmp.move_elephant_to(ne));
-            }
-        }
-        // nothing + move
-        for ne in solver.leads_to(&self.elephant) {
-            nexts.push(next.move_elephant_to(ne));
-        }
-        nexts
+    fn dominates(&self, state: &State, other: &State) -> bool {
+        state.released >= other.released
     }
 }
 
 impl AdventSolver for Advent2022Day16Solver {
     fn solve_part1(&self) -> usize {
-        let mut all_states = vec![State::new()];
-        for _ in 0..30 {
-            let new_states: HashSet<State> = all_states
-                .iter()
-                .flat_map(|s| s.next_states_alone(self))
-                .collect();
-            all_states = new_states
-                .iter()
-                .sorted_by(|l, r| usize::cmp(&r.pressure_total, &l.pressure_total))
-                .take(100000)
-                .cloned()
-                .collect();
-        }
-        all_states.iter().map(|s| s.pressure_total).max().unwrap()
+        self.most_pressure(30, 1).0
     }
 
     fn solve_part2(&self) -> usize {
-        let mut all_states = vec![State::new()];
-        for i in 0..26 {
-            let cutoff = if i < 15 { 1000000 } else { 100000 };
-            let new_states: HashSet<State> = all_states
-                .iter()
-                .flat_map(|s| s.next_states(self))
-                .collect();
-            all_states = new_states
-                .iter()
-                .sorted_by(|l, r| {
-                    usize::cmp(&r.pressure, &l.pressure)
-                        .then(usize::cmp(&r.pressure_total, &l.pressure_total))
-                })
-                .take(cutoff)
-                .cloned()
-                .collect();
-        }
-        all_states.iter().map(|s| s.pressure_total).max().unwrap()
+        self.most_pressure(26, 2).0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+    #[test]
+    fn finds_most_pressure_released() {
+        let solver = Advent2022Day16Solver::new(EXAMPLE);
+        assert_eq!(solver.solve_part1(), 1651);
+        assert_eq!(solver.solve_part2(), 1707);
+    }
+
+    #[test]
+    fn prunes_valve_orders_that_cannot_beat_the_best() {
+        let solver = Advent2022Day16Solver::new(EXAMPLE);
+        assert_eq!(
+            solver.most_pressure(30, 1).1,
+            SearchStats {
+                expanded: 64,
+                pruned: 195
+            }
+        );
+        assert_eq!(
+            solver.most_pressure(26, 2).1,
+            SearchStats {
+                expanded: 81,
+                pruned: 261
+            }
+        );
     }
 }
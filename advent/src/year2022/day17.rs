@@ -1,4 +1,5 @@
 use crate::solver::AdventSolver;
+use crate::visualize::{BLACK, Frame, FrameSink, GRAY, Visualize, YELLOW};
 
 #[derive(Clone, Copy, Debug)]
 enum Direction {
@@ -122,6 +123,17 @@ impl Chamber {
         println!();
     }
 
+    /// The top `height` rows of the chamber, ground at the bottom.
+    fn frame(&self, height: usize) -> Frame {
+        Frame::from_fn(7, height, |x, y| {
+            match self.rows.len().checked_sub(height - y) {
+                Some(r) if self.rows[r][x] == State::Resting => GRAY,
+                Some(r) if self.rows[r][x] == State::Falling => YELLOW,
+                _ => BLACK,
+            }
+        })
+    }
+
     fn switch_state(&mut self, shape: &Shape, top: usize, left: usize, state: State) {
         shape
             .occupies
@@ -237,4 +249,21 @@ impl AdventSolver for Advent2022Day17Solver {
         }
         chamber.rows.len() - 1 + chamber.truncated_rows
     }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
+}
+
+impl Visualize for Advent2022Day17Solver {
+    /// Drops the rocks of part 1, one frame per rock.
+    fn visualize(&self, sink: &mut dyn FrameSink) -> std::io::Result<()> {
+        const HEIGHT: usize = 60;
+        let mut chamber = Chamber::new(&self.jet_pattern);
+        for shape in self.shapes.iter().cycle().take(2022) {
+            chamber.drop(shape);
+            sink.frame(&chamber.frame(HEIGHT))?;
+        }
+        Ok(())
+    }
 }
@@ -1,10 +1,9 @@
-use std::cmp::Ordering;
-use std::collections::HashSet;
 use std::fmt::{Debug, Formatter};
 
-use itertools::Itertools;
 use regex::Regex;
 
+use crate::common::parallel;
+use crate::common::search::{BranchAndBound, SearchStats, Strategy, branch_and_bound};
 use crate::solver::AdventSolver;
 
 pub struct Advent2022Day19Solver {
@@ -46,146 +45,181 @@ struct Blueprint {
 }
 
 impl Blueprint {
-    fn geode_count(&self, length: usize) -> usize {
-        let mut states: HashSet<State> = HashSet::new();
-        states.insert(State::new());
-        for _ in 0..length {
-            let next_states: HashSet<State> =
-                states.iter().flat_map(|s| s.next_states(self)).collect();
-            states = next_states
-                .iter()
-                .sorted_by(|a, b| State::cmp(b, a))
-                .take(10000)
-                .cloned()
-                .collect();
-        }
-        states.iter().map(|s| s.geode_count).max().unwrap()
+    fn geode_count(&self, length: usize) -> (usize, SearchStats) {
+        let outcome = branch_and_bound(self, State::new(length), Strategy::DepthFirst);
+        (outcome.value().unwrap_or(0), outcome.stats())
+    }
+
+    /// Ore, clay and obsidian costs of the ore, clay, obsidian and geode robots.
+    fn costs(&self) -> [[usize; 3]; 4] {
+        [
+            [self.ore_robot_ore_cost, 0, 0],
+            [self.clay_robot_ore_cost, 0, 0],
+            [
+                self.obsidian_robot_ore_cost,
+                self.obsidian_robot_clay_cost,
+                0,
+            ],
+            [self.geode_robot_ore_cost, 0, self.geode_robot_obsidian_cost],
+        ]
     }
 }
 
+/// Robots and resources for ore, clay, obsidian and geodes.
 #[derive(Clone, Eq, Hash, PartialEq)]
 struct State {
-    ore_robots: usize,
-    clay_robots: usize,
-    obsidian_robots: usize,
-    geode_robots: usize,
-    ore_count: usize,
-    clay_count: usize,
-    obsidian_count: usize,
-    geode_count: usize,
+    time_left: usize,
+    robots: [usize; 4],
+    resources: [usize; 4],
 }
 
 impl Debug for State {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("States")
-            .field(
-                "count",
-                &(
-                    self.ore_count,
-                    self.clay_count,
-                    self.obsidian_count,
-                    self.geode_count,
-                ),
-            )
-            .field(
-                "robots",
-                &(
-                    self.ore_robots,
-                    self.clay_robots,
-                    self.obsidian_robots,
-                    self.geode_robots,
-                ),
-            )
+        f.debug_struct("State")
+            .field("time_left", &self.time_left)
+            .field("resources", &self.resources)
+            .field("robots", &self.robots)
             .finish()
     }
 }
 
 impl State {
-    fn new() -> Self {
-        Self {
-            ore_robots: 1,
-            clay_robots: 0,
-            obsidian_robots: 0,
-            geode_robots: 0,
-            ore_count: 0,
-            clay_count: 0,
-            obsidian_count: 0,
-            geode_count: 0,
-        }
-    }
-
-    fn cmp(&self, other: &Self) -> Ordering {
-        usize::cmp(&self.geode_count, &other.geode_count)
-            .then(usize::cmp(&self.geode_robots, &other.geode_robots))
-            .then(usize::cmp(&self.obsidian_robots, &other.obsidian_robots))
-            .then(usize::cmp(&self.clay_robots, &other.clay_robots))
-            .then(usize::cmp(&self.ore_robots, &other.ore_robots))
-    }
-
-    fn next(&self) -> Self {
+    fn new(time_left: usize) -> Self {
         Self {
-            ore_robots: self.ore_robots,
-            clay_robots: self.clay_robots,
-            obsidian_robots: self.obsidian_robots,
-            geode_robots: self.geode_robots,
-            ore_count: self.ore_count + self.ore_robots,
-            clay_count: self.clay_count + self.clay_robots,
-            obsidian_count: self.obsidian_count + self.obsidian_robots,
-            geode_count: self.geode_count + self.geode_robots,
+            time_left,
+            robots: [1, 0, 0, 0],
+            resources: [0; 4],
         }
     }
 
-    fn next_states(&self, blueprint: &Blueprint) -> Vec<State> {
-        let mut nexts = vec![];
-        let next = self.next();
-        nexts.push(next.clone());
-        if self.ore_count >= blueprint.ore_robot_ore_cost {
-            let mut tmp = next.clone();
-            tmp.ore_robots += 1;
-            tmp.ore_count -= blueprint.ore_robot_ore_cost;
-            nexts.push(tmp);
+    /// Waits as long as needed to build `robot`, `None` if that cannot happen in time.
+    fn build(&self, robot: usize, cost: &[usize; 3]) -> Option<Self> {
+        let mut wait = 0;
+        for resource in 0..3 {
+            if cost[resource] > self.resources[resource] {
+                if self.robots[resource] == 0 {
+                    return None;
+                }
+                wait = wait.max(
+                    (cost[resource] - self.resources[resource]).div_ceil(self.robots[resource]),
+                );
+            }
         }
-        if self.ore_count >= blueprint.clay_robot_ore_cost {
-            let mut tmp = next.clone();
-            tmp.clay_robots += 1;
-            tmp.ore_count -= blueprint.clay_robot_ore_cost;
-            nexts.push(tmp);
+        if wait + 1 >= self.time_left {
+            return None;
         }
-        if self.ore_count >= blueprint.obsidian_robot_ore_cost
-            && self.clay_count >= blueprint.obsidian_robot_clay_cost
-        {
-            let mut tmp = next.clone();
-            tmp.obsidian_robots += 1;
-            tmp.ore_count -= blueprint.obsidian_robot_ore_cost;
-            tmp.clay_count -= blueprint.obsidian_robot_clay_cost;
-            nexts.push(tmp);
+        let mut next = self.clone();
+        next.time_left -= wait + 1;
+        for resource in 0..4 {
+            next.resources[resource] += self.robots[resource] * (wait + 1);
         }
-        if self.ore_count >= blueprint.geode_robot_ore_cost
-            && self.obsidian_count >= blueprint.geode_robot_obsidian_cost
-        {
-            let mut tmp = next.clone();
-            tmp.geode_robots += 1;
-            tmp.ore_count -= blueprint.geode_robot_ore_cost;
-            tmp.obsidian_count -= blueprint.geode_robot_obsidian_cost;
-            nexts.push(tmp);
+        for resource in 0..3 {
+            next.resources[resource] -= cost[resource];
         }
-        nexts
+        next.robots[robot] += 1;
+        Some(next)
+    }
+}
+
+impl BranchAndBound for Blueprint {
+    type Node = State;
+    type Key = (usize, [usize; 4]);
+    type Value = usize;
+
+    fn successors(&self, state: &State) -> Vec<State> {
+        let costs = self.costs();
+        (0..4)
+            .filter(|&robot| {
+                // no point producing more of a resource than can be spent in a minute
+                robot == 3 || costs.iter().any(|c| c[robot] > state.robots[robot])
+            })
+            .filter_map(|robot| state.build(robot, &costs[robot]))
+            .collect()
+    }
+
+    /// Geodes opened by the end when building nothing more.
+    fn value(&self, state: &State) -> Option<usize> {
+        Some(state.resources[3] + state.robots[3] * state.time_left)
+    }
+
+    /// As if a geode robot could be built every remaining minute.
+    fn bound(&self, state: &State) -> usize {
+        self.value(state).unwrap() + state.time_left * state.time_left.saturating_sub(1) / 2
+    }
+
+    fn key(&self, state: &State) -> (usize, [usize; 4]) {
+        (state.time_left, state.robots)
+    }
+
+    fn dominates(&self, state: &State, other: &State) -> bool {
+        (0..4).all(|r| state.resources[r] >= other.resources[r])
     }
 }
 
 impl AdventSolver for Advent2022Day19Solver {
     fn solve_part1(&self) -> usize {
-        self.blueprints
-            .iter()
-            .map(|b| b.geode_count(24) * b.number)
-            .sum()
+        self.quality_levels().0
     }
 
     fn solve_part2(&self) -> usize {
-        self.blueprints
+        self.first_geodes().0
+    }
+}
+
+impl Advent2022Day19Solver {
+    fn quality_levels(&self) -> (usize, SearchStats) {
+        let counts = parallel::map(&self.blueprints, |b| b.geode_count(24));
+        let quality = self
+            .blueprints
             .iter()
-            .take(3)
-            .map(|b| b.geode_count(32))
-            .product::<usize>()
+            .zip(&counts)
+            .map(|(b, (geodes, _))| geodes * b.number)
+            .sum();
+        (quality, counts.into_iter().map(|(_, stats)| stats).sum())
+    }
+
+    fn first_geodes(&self) -> (usize, SearchStats) {
+        let first = &self.blueprints[..self.blueprints.len().min(3)];
+        let counts = parallel::map(first, |b| b.geode_count(32));
+        let geodes = counts.iter().map(|(geodes, _)| geodes).product();
+        (geodes, counts.into_iter().map(|(_, stats)| stats).sum())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+
+    #[test]
+    fn finds_most_geodes() {
+        let solver = Advent2022Day19Solver::new(EXAMPLE);
+        assert_eq!(solver.blueprints[0].geode_count(24).0, 9);
+        assert_eq!(solver.blueprints[1].geode_count(24).0, 12);
+        assert_eq!(solver.solve_part1(), 33);
+        assert_eq!(solver.blueprints[0].geode_count(32).0, 56);
+        assert_eq!(solver.blueprints[1].geode_count(32).0, 62);
+    }
+
+    #[test]
+    fn prunes_build_orders_that_cannot_beat_the_best() {
+        let solver = Advent2022Day19Solver::new(EXAMPLE);
+        assert_eq!(
+            solver.quality_levels().1,
+            SearchStats {
+                expanded: 8626,
+                pruned: 17462
+            }
+        );
+        assert_eq!(
+            solver.first_geodes().1,
+            SearchStats {
+                expanded: 72183,
+                pruned: 173125
+            }
+        );
     }
 }
@@ -1,10 +1,8 @@
+use crate::common::intervals::{IntervalMap, IntervalSet};
 use crate::solver::AdventSolver;
-use itertools::Itertools;
-use std::cmp::Ordering;
-use std::collections::VecDeque;
 
 pub struct Advent2023Day05Solver {
-    seeds: Vec<u32>,
+    seeds: Vec<u64>,
     almanac: Almanac,
 }
 
@@ -14,6 +12,7 @@ impl Advent2023Day05Solver {
         Self {
             seeds: split
                 .0
+                .trim_end()
                 .split(" ")
                 .skip(1)
                 .map(|n| n.parse().unwrap())
@@ -33,232 +32,48 @@ impl AdventSolver for Advent2023Day05Solver {
     }
 
     fn solve_part2(&self) -> usize {
-        let mut seed_ranges = Vec::new();
-        for i in 0..self.seeds.len() / 2 {
-            seed_ranges.push(self.seeds[i * 2]..self.seeds[i * 2] + self.seeds[i * 2 + 1]);
-        }
+        let seed_ranges = self
+            .seeds
+            .chunks(2)
+            .map(|c| c[0]..=c[0] + c[1] - 1)
+            .collect();
         self.almanac.seed_range_location(&seed_ranges) as usize
     }
 }
 
 struct Almanac {
-    seed_location: Transform,
+    maps: Vec<IntervalMap<u64>>,
 }
 
 impl Almanac {
-    fn new(transforms: Vec<Transform>) -> Self {
-        Self {
-            seed_location: transforms
-                .iter()
-                .fold(Transform::init(), |acc, cur| acc.merge(cur)),
-        }
-    }
-
-    fn seed_location(&self, seed: u32) -> u32 {
-        self.seed_location.transform(seed)
-    }
-
-    fn seed_range_location(&self, seeds: &[std::ops::Range<u32>]) -> u32 {
-        self.seed_location.lowest_matching(seeds).destination
-    }
-}
-
-struct Transform {
-    ranges: Vec<Range>,
-}
-
-impl Transform {
-    fn init() -> Self {
-        Self {
-            ranges: vec![Range {
-                source: 0,
-                destination: 0,
-                length: u32::MAX,
-            }],
-        }
-    }
-
-    fn new(ranges: Vec<Range>) -> Self {
-        let mut ranges = ranges;
-        ranges.sort_by_key(|r| r.source);
-        if ranges[0].source != 0 {
-            ranges.insert(
-                0,
-                Range {
-                    source: 0,
-                    destination: 0,
-                    length: ranges[0].source,
-                },
-            );
-        }
-        let mut i = 1;
-        while i < ranges.len() {
-            let last_source = ranges[i - 1].source + ranges[i - 1].length;
-            if ranges[i].source != last_source {
-                ranges.insert(
-                    i,
-                    Range {
-                        source: last_source,
-                        destination: last_source,
-                        length: ranges[i].source - last_source,
-                    },
-                );
-            }
-            i += 1;
-        }
-        let last_source = ranges[i - 1].source - 1 + ranges[i - 1].length;
-        if last_source != u32::MAX {
-            ranges.push(Range {
-                source: last_source,
-                destination: last_source,
-                length: u32::MAX - last_source,
-            })
-        }
-        Self { ranges }
-    }
-
-    fn transform(&self, input: u32) -> u32 {
-        self.ranges
-            .iter()
-            .filter_map(|r| r.to_destination(input))
-            .next()
-            .unwrap()
+    fn seed_location(&self, seed: u64) -> u64 {
+        self.maps.iter().fold(seed, |acc, map| map.map(acc))
     }
 
-    fn lowest_matching(&self, ranges: &[std::ops::Range<u32>]) -> &Range {
-        self.ranges
+    fn seed_range_location(&self, seeds: &IntervalSet<u64>) -> u64 {
+        self.maps
             .iter()
-            .sorted_by_key(|r| r.destination)
-            .find(|r| ranges.iter().any(|s| s.contains(&r.source)))
+            .fold(seeds.clone(), |acc, map| map.map_set(&acc))
+            .min()
             .unwrap()
     }
-
-    fn merge(&self, next: &Self) -> Self {
-        let mut self_ranges: VecDeque<Range> = self
-            .ranges
-            .iter()
-            .cloned()
-            .sorted_by_key(|r| r.destination)
-            .collect();
-        let mut next_ranges: VecDeque<Range> = next
-            .ranges
-            .iter()
-            .cloned()
-            .sorted_by_key(|r| r.source)
-            .collect();
-        let mut ranges = Vec::new();
-        while !self_ranges.is_empty() && !next_ranges.is_empty() {
-            let sr = self_ranges.pop_front().unwrap();
-            let nr = next_ranges.pop_front().unwrap();
-            match sr.length.cmp(&nr.length) {
-                Ordering::Equal => {
-                    ranges.push(Range {
-                        source: sr.source,
-                        destination: nr.destination,
-                        length: sr.length,
-                    });
-                }
-                Ordering::Less => {
-                    ranges.push(Range {
-                        source: sr.source,
-                        destination: nr.destination,
-                        length: sr.length,
-                    });
-                    next_ranges.push_front(Range {
-                        source: nr.source + sr.length,
-                        destination: nr.destination + sr.length,
-                        length: nr.length - sr.length,
-                    });
-                }
-                Ordering::Greater => {
-                    ranges.push(Range {
-                        source: sr.source,
-                        destination: nr.destination,
-                        length: nr.length,
-                    });
-                    self_ranges.push_front(Range {
-                        source: sr.source + nr.length,
-                        destination: sr.destination + nr.length,
-                        length: sr.length - nr.length,
-                    });
-                }
-            }
-        }
-        ranges.sort_by_key(|r| r.source);
-        Self { ranges }
-    }
-}
-
-#[derive(Debug, Clone)]
-struct Range {
-    destination: u32,
-    source: u32,
-    length: u32,
-}
-
-impl Range {
-    fn to_destination(&self, input: u32) -> Option<u32> {
-        if input < self.source {
-            return None;
-        }
-        let offset = input - self.source;
-        if offset < self.length {
-            Some(self.destination + offset)
-        } else {
-            None
-        }
-    }
 }
 
 impl From<&str> for Almanac {
     fn from(value: &str) -> Self {
-        let mut seed_soil = Vec::new();
-        let mut soil_fertilizer = Vec::new();
-        let mut fertilizer_water = Vec::new();
-        let mut water_light = Vec::new();
-        let mut light_temperature = Vec::new();
-        let mut temperature_humidity = Vec::new();
-        let mut humidity_location = Vec::new();
-        let mut state: &str = "";
-        for line in value.lines().filter(|l| !l.is_empty()) {
-            match (state, line.split(" ").next().unwrap()) {
-                ("", "seed-to-soil") => state = "ss",
-                ("ss", "soil-to-fertilizer") => state = "sf",
-                ("ss", _) => seed_soil.push(Range::from(line)),
-                ("sf", "fertilizer-to-water") => state = "fw",
-                ("sf", _) => soil_fertilizer.push(Range::from(line)),
-                ("fw", "water-to-light") => state = "wl",
-                ("fw", _) => fertilizer_water.push(Range::from(line)),
-                ("wl", "light-to-temperature") => state = "lt",
-                ("wl", _) => water_light.push(Range::from(line)),
-                ("lt", "temperature-to-humidity") => state = "th",
-                ("lt", _) => light_temperature.push(Range::from(line)),
-                ("th", "humidity-to-location") => state = "hl",
-                ("th", _) => temperature_humidity.push(Range::from(line)),
-                ("hl", _) => humidity_location.push(Range::from(line)),
-                (s, _) => panic!("unknown map state {s}, {line}"),
+        // blocks are read line by line so that CRLF input parses too
+        let mut maps: Vec<IntervalMap<u64>> = Vec::new();
+        for line in value.lines().filter(|l| !l.trim().is_empty()) {
+            if line.ends_with("map:") {
+                maps.push(IntervalMap::new());
+                continue;
             }
+            let numbers: Vec<u64> = line.split(" ").map(|s| s.parse().unwrap()).collect();
+            maps.last_mut()
+                .unwrap()
+                .insert(numbers[1]..=numbers[1] + numbers[2] - 1, numbers[0]);
         }
-        Almanac::new(vec![
-            Transform::new(seed_soil),
-            Transform::new(soil_fertilizer),
-            Transform::new(fertilizer_water),
-            Transform::new(water_light),
-            Transform::new(light_temperature),
-            Transform::new(temperature_humidity),
-            Transform::new(humidity_location),
-        ])
-    }
-}
-
-impl From<&str> for Range {
-    fn from(value: &str) -> Self {
-        let numbers: Vec<u32> = value.split(" ").map(|s| s.parse().unwrap()).collect();
-        Range {
-            destination: numbers[0],
-            source: numbers[1],
-            length: numbers[2],
-        }
+        Self { maps }
     }
 }
 
@@ -305,7 +120,7 @@ humidity-to-location map:
     #[test]
     fn simple_seeds() {
         let solver = Advent2023Day05Solver::new(EXAMPLE);
-        let locations: Vec<u32> = solver
+        let locations: Vec<u64> = solver
             .seeds
             .iter()
             .map(|seed| solver.almanac.seed_location(*seed))
@@ -319,4 +134,11 @@ humidity-to-location map:
         let solver = Advent2023Day05Solver::new(EXAMPLE);
         assert_eq!(solver.solve_part2(), 46);
     }
+
+    #[test]
+    fn reads_crlf_input() {
+        let solver = Advent2023Day05Solver::new(&EXAMPLE.replace('\n', "\r\n"));
+        assert_eq!(solver.solve_part1(), 35);
+        assert_eq!(solver.solve_part2(), 46);
+    }
 }
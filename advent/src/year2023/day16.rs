@@ -1,5 +1,6 @@
 use Direction::*;
 
+use crate::common::parallel;
 use crate::solver::AdventSolver;
 
 pub struct Advent2023Day16Solver {
@@ -20,32 +21,15 @@ impl AdventSolver for Advent2023Day16Solver {
     }
 
     fn solve_part2(&self) -> usize {
-        let mut highest = 0;
-        for y in 0..=self.contraption.max_y {
-            let right = self.contraption.energized_squares((0, y, Right));
-            if right > highest {
-                highest = right;
-            }
-            let left = self
-                .contraption
-                .energized_squares((self.contraption.max_x, y, Left));
-            if left > highest {
-                highest = left;
-            }
-        }
-        for x in 0..=self.contraption.max_x {
-            let down = self.contraption.energized_squares((x, 0, Down));
-            if down > highest {
-                highest = down;
-            }
-            let up = self
-                .contraption
-                .energized_squares((x, self.contraption.max_y, Up));
-            if up > highest {
-                highest = up;
-            }
-        }
-        highest
+        let (max_x, max_y) = (self.contraption.max_x, self.contraption.max_y);
+        let entries: Vec<Beam> = (0..=max_y)
+            .flat_map(|y| [(0, y, Right), (max_x, y, Left)])
+            .chain((0..=max_x).flat_map(|x| [(x, 0, Down), (x, max_y, Up)]))
+            .collect();
+        parallel::map(&entries, |&beam| self.contraption.energized_squares(beam))
+            .into_iter()
+            .max()
+            .unwrap()
     }
 }
 
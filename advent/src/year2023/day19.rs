@@ -1,8 +1,8 @@
 use std::collections::{HashMap, VecDeque};
-use std::ops::RangeInclusive;
 
 use regex::{Match, Regex};
 
+use crate::common::intervals::IntervalSet;
 use crate::solver::AdventSolver;
 
 pub struct Advent2023Day19Solver {
@@ -93,10 +93,10 @@ struct WorkflowEngine {
 
 #[derive(Clone)]
 struct WorkflowRange {
-    x: RangeInclusive<u64>,
-    m: RangeInclusive<u64>,
-    a: RangeInclusive<u64>,
-    s: RangeInclusive<u64>,
+    x: IntervalSet<u64>,
+    m: IntervalSet<u64>,
+    a: IntervalSet<u64>,
+    s: IntervalSet<u64>,
 }
 
 impl WorkflowEngine {
@@ -104,10 +104,10 @@ impl WorkflowEngine {
         let mut reversed: VecDeque<(WorkflowRange, String)> = VecDeque::new();
         reversed.push_back((
             WorkflowRange {
-                x: 1..=4000,
-                m: 1..=4000,
-                a: 1..=4000,
-                s: 1..=4000,
+                x: IntervalSet::from(1..=4000),
+                m: IntervalSet::from(1..=4000),
+                a: IntervalSet::from(1..=4000),
+                s: IntervalSet::from(1..=4000),
             },
             "in".to_string(),
         ));
@@ -130,7 +130,7 @@ impl WorkflowEngine {
     }
 
     fn count_accepted(&self) -> usize {
-        self.accepted.iter().cloned().map(|wr| wr.count()).sum()
+        self.accepted.iter().map(|wr| wr.count()).sum()
     }
 }
 
@@ -142,76 +142,42 @@ impl WorkflowRange {
             && self.s.contains(&part.s)
     }
 
-    fn count(self) -> usize {
-        self.x.count() * self.m.count() * self.a.count() * self.s.count()
+    fn count(&self) -> usize {
+        (self.x.len() * self.m.len() * self.a.len() * self.s.len()) as usize
     }
 
-    fn split(&self, rule: &Rule) -> Option<(Self, Self)> {
+    fn var_mut(&mut self, var: char) -> &mut IntervalSet<u64> {
+        match var {
+            'x' => &mut self.x,
+            'm' => &mut self.m,
+            'a' => &mut self.a,
+            's' => &mut self.s,
+            _ => panic!("unknown variable {var}"),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.x.is_empty() || self.m.is_empty() || self.a.is_empty() || self.s.is_empty()
+    }
+
+    fn split(&self, rule: &Rule) -> (Self, Self) {
+        let matching = match rule.op {
+            '>' => match rule.val.checked_add(1) {
+                Some(min) => IntervalSet::from(min..=u64::MAX),
+                None => IntervalSet::new(),
+            },
+            '<' => match rule.val.checked_sub(1) {
+                Some(max) => IntervalSet::from(0..=max),
+                None => IntervalSet::new(),
+            },
+            _ => panic!("unknown operation {} {} {}", rule.var, rule.op, rule.val),
+        };
         let mut included = self.clone();
         let mut excluded = self.clone();
-        match (rule.var, rule.op) {
-            ('x', '>') => {
-                if !self.x.contains(&rule.val) {
-                    return None;
-                }
-                included.x = rule.val + 1..=*self.x.end();
-                excluded.x = *self.x.start()..=rule.val;
-            }
-            ('x', '<') => {
-                if !self.x.contains(&rule.val) {
-                    return None;
-                }
-                included.x = *self.x.start()..=rule.val - 1;
-                excluded.x = rule.val..=*self.x.end();
-            }
-            ('m', '>') => {
-                if !self.m.contains(&rule.val) {
-                    return None;
-                }
-                included.m = rule.val + 1..=*self.m.end();
-                excluded.m = *self.m.start()..=rule.val;
-            }
-            ('m', '<') => {
-                if !self.m.contains(&rule.val) {
-                    return None;
-                }
-                included.m = *self.m.start()..=rule.val - 1;
-                excluded.m = rule.val..=*self.m.end();
-            }
-            ('a', '>') => {
-                if !self.a.contains(&rule.val) {
-                    return None;
-                }
-                included.a = rule.val + 1..=*self.a.end();
-                excluded.a = *self.a.start()..=rule.val;
-            }
-            ('a', '<') => {
-                if !self.a.contains(&rule.val) {
-                    return None;
-                }
-                included.a = *self.a.start()..=rule.val - 1;
-                excluded.a = rule.val..=*self.a.end();
-            }
-            ('s', '>') => {
-                if !self.s.contains(&rule.val) {
-                    return None;
-                }
-                included.s = rule.val + 1..=*self.s.end();
-                excluded.s = *self.s.start()..=rule.val;
-            }
-            ('s', '<') => {
-                if !self.s.contains(&rule.val) {
-                    return None;
-                }
-                included.s = *self.s.start()..=rule.val - 1;
-                excluded.s = rule.val..=*self.s.end();
-            }
-            _ => panic!(
-                "unknown splitting combination {} {} {}",
-                rule.var, rule.op, rule.val
-            ),
-        };
-        Some((included, excluded))
+        let values = included.var_mut(rule.var);
+        *excluded.var_mut(rule.var) = values.difference(&matching);
+        *values = values.intersection(&matching);
+        (included, excluded)
     }
 }
 
@@ -225,14 +191,13 @@ impl Workflow {
         let mut reversed = vec![];
         let mut current = from.clone();
         for rule in &self.rules {
-            if let Some((included, excluded)) = current.split(rule) {
-                if rule.to != "R" {
-                    reversed.push((included, rule.to.clone()));
-                }
-                current = excluded;
+            let (included, excluded) = current.split(rule);
+            if rule.to != "R" && !included.is_empty() {
+                reversed.push((included, rule.to.clone()));
             }
+            current = excluded;
         }
-        if self.last != "R" {
+        if self.last != "R" && !current.is_empty() {
             reversed.push((current, self.last.clone()));
         }
         reversed
@@ -281,4 +246,10 @@ hdj{m>838:A,pv}
         let solver = Advent2023Day19Solver::new(EXAMPLE);
         assert_eq!(solver.solve_part2(), 167409079868000);
     }
+
+    #[test]
+    fn accepts_rules_at_the_value_bounds() {
+        let solver = Advent2023Day19Solver::new("in{x<0:R,m>18446744073709551615:R,A}\n\n{x=1,m=2,a=3,s=4}");
+        assert_eq!(solver.solve_part1(), 10);
+    }
 }
@@ -1,8 +1,9 @@
-use std::collections::{HashMap, VecDeque};
-use std::fmt::{Debug, Formatter};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{Debug, Formatter, Write};
 
 use itertools::Itertools;
 
+use crate::common::modular::crt;
 use crate::solver::AdventSolver;
 
 pub struct Advent2023Day20Solver {
@@ -39,100 +40,268 @@ impl Advent2023Day20Solver {
     }
 
     fn new_configuration(&self) -> ModuleConfiguration {
+        ModuleConfiguration::new(self.build_modules(self.broadcaster.clone(), |_| true), None)
+    }
+
+    fn build_modules(
+        &self,
+        broadcast: Vec<ModuleName>,
+        include: impl Fn(&ModuleName) -> bool,
+    ) -> HashMap<ModuleName, Box<dyn Module>> {
         let mut modules: HashMap<ModuleName, Box<dyn Module>> = HashMap::new();
-        modules.insert(
-            String::from("broadcaster"),
-            Box::new(Broadcaster::new(self.broadcaster.clone())),
-        );
-        let rx_conjunction = self
-            .conjunctions
-            .iter()
-            .find(|(_, d)| d.contains(&String::from("rx")));
-        let rx_requirements = if let Some((rx_conjunction, _)) = rx_conjunction {
-            self.conjunctions
-                .iter()
-                .filter(|(_, d)| d.contains(rx_conjunction))
-                .map(|(n, _)| n.clone())
-                .collect()
-        } else {
-            Vec::new()
-        };
-        for (ffn, ffd) in &self.flip_flops {
+        for (ffn, ffd) in self.flip_flops.iter().filter(|(n, _)| include(n)) {
             modules.insert(ffn.to_string(), Box::new(FlipFlop::new(ffd.clone())));
         }
-        for (cn, cd) in &self.conjunctions {
+        for (cn, cd) in self.conjunctions.iter().filter(|(n, _)| include(n)) {
             let mut inputs = vec![];
-            if self.broadcaster.contains(cn) {
+            if broadcast.contains(cn) {
                 inputs.push(String::from("broadcaster"));
             }
-            inputs.extend(
-                self.flip_flops
-                    .iter()
-                    .filter(|(_, d)| d.contains(cn))
-                    .map(|(n, _)| n.clone()),
-            );
-            inputs.extend(
-                self.conjunctions
-                    .iter()
-                    .filter(|(_, d)| d.contains(cn))
-                    .map(|(n, _)| n.clone()),
-            );
+            inputs.extend(self.inputs(cn).into_iter().filter(|n| include(n)));
             modules.insert(
                 cn.to_string(),
                 Box::new(Conjunction::new(inputs, cd.clone())),
             );
         }
-        ModuleConfiguration::new(modules, rx_requirements)
+        modules.insert(
+            String::from("broadcaster"),
+            Box::new(Broadcaster::new(broadcast)),
+        );
+        modules
+    }
+
+    fn destinations(&self, name: &str) -> &[ModuleName] {
+        if name == "broadcaster" {
+            return &self.broadcaster;
+        }
+        self.flip_flops
+            .get(name)
+            .or_else(|| self.conjunctions.get(name))
+            .map_or(&[], |d| d.as_slice())
+    }
+
+    fn inputs(&self, name: &str) -> Vec<ModuleName> {
+        self.flip_flops
+            .iter()
+            .chain(self.conjunctions.iter())
+            .filter(|(_, d)| d.iter().any(|n| n == name))
+            .map(|(n, _)| n.clone())
+            .sorted()
+            .collect()
+    }
+
+    /// Splits the network into the independent counters started by the broadcaster, which all
+    /// need to send a high pulse to the conjunction feeding `rx` on the same button press.
+    fn counters(&self) -> Result<Vec<SubCounter>, NetworkError> {
+        let sinks = self.inputs("rx");
+        let sink = match &sinks[..] {
+            [] => return Err(NetworkError::NoRx),
+            [sink] if self.conjunctions.contains_key(sink) => sink.clone(),
+            _ => return Err(NetworkError::RxNotFedByConjunction),
+        };
+        let mut counters = Vec::new();
+        let mut seen: HashSet<ModuleName> = HashSet::new();
+        for start in &self.broadcaster {
+            let mut modules = HashSet::from([start.clone()]);
+            let mut queue = VecDeque::from([start.clone()]);
+            while let Some(current) = queue.pop_front() {
+                for next in self.destinations(&current) {
+                    if *next != sink && *next != "rx" && modules.insert(next.clone()) {
+                        queue.push_back(next.clone());
+                    }
+                }
+            }
+            let outputs = modules
+                .iter()
+                .filter(|m| self.destinations(m).contains(&sink))
+                .cloned()
+                .collect_vec();
+            let external = modules
+                .iter()
+                .any(|m| self.inputs(m).iter().any(|i| !modules.contains(i)));
+            if outputs.len() != 1 || external || modules.iter().any(|m| seen.contains(m)) {
+                return Err(NetworkError::DependentCounter(start.clone()));
+            }
+            seen.extend(modules.iter().cloned());
+            counters.push(self.simulate_counter(start, modules, &sink, outputs[0].clone())?);
+        }
+        if self.inputs(&sink).iter().any(|i| !seen.contains(i)) {
+            return Err(NetworkError::DependentCounter(sink));
+        }
+        Ok(counters)
+    }
+
+    fn simulate_counter(
+        &self,
+        start: &ModuleName,
+        modules: HashSet<ModuleName>,
+        sink: &ModuleName,
+        output: ModuleName,
+    ) -> Result<SubCounter, NetworkError> {
+        let mut configuration = ModuleConfiguration::new(
+            self.build_modules(vec![start.clone()], |n| modules.contains(n)),
+            Some(sink.clone()),
+        );
+        let mut states = HashMap::from([(configuration.state(), 0)]);
+        let mut hits = Vec::new();
+        for presses in 1..=MAX_COUNTER_PRESSES {
+            if configuration.push_button() {
+                hits.push(presses);
+            }
+            if let Some(&offset) = states.get(&configuration.state()) {
+                return Ok(SubCounter {
+                    start: start.clone(),
+                    output,
+                    offset,
+                    period: presses - offset,
+                    phases: hits.into_iter().filter(|&h| h > offset).collect(),
+                });
+            }
+            states.insert(configuration.state(), presses);
+        }
+        Err(NetworkError::NoPeriod(start.clone()))
+    }
+
+    fn rx_low_button_presses(&self) -> Result<usize, NetworkError> {
+        let counters = self.counters()?;
+        let earliest = counters.iter().map(|c| c.offset + 1).max().unwrap_or(1) as i128;
+        counters
+            .iter()
+            .map(|c| c.phases.iter().map(move |&p| (p as i128, c.period as i128)))
+            .multi_cartesian_product()
+            .filter_map(crt)
+            .map(|(residue, modulus)| {
+                let behind = (earliest - residue).max(0);
+                residue + (behind + modulus - 1) / modulus * modulus
+            })
+            .min()
+            .map(|presses| presses as usize)
+            .ok_or(NetworkError::NeverLow)
+    }
+
+    /// One line per independent counter: the module it starts from, the one reporting to the
+    /// sink, and when it does so.
+    pub fn counter_report(&self) -> Result<String, NetworkError> {
+        let mut report = String::new();
+        for c in self.counters()? {
+            let _ = writeln!(
+                report,
+                "{} -> {}: offset {}, period {}, phases {:?}",
+                c.start, c.output, c.offset, c.period, c.phases
+            );
+        }
+        Ok(report)
+    }
+
+    /// Graphviz representation of the network, handy to see how the counters are wired.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph modules {\n  broadcaster [shape=doublecircle];\n");
+        for name in self.flip_flops.keys().sorted() {
+            let _ = writeln!(dot, "  {name} [shape=box, label=\"%{name}\"];");
+        }
+        for name in self.conjunctions.keys().sorted() {
+            let _ = writeln!(dot, "  {name} [shape=diamond, label=\"&{name}\"];");
+        }
+        let sources = ["broadcaster".to_string()]
+            .into_iter()
+            .chain(self.flip_flops.keys().cloned())
+            .chain(self.conjunctions.keys().cloned())
+            .sorted();
+        for source in sources {
+            for destination in self.destinations(&source) {
+                let _ = writeln!(dot, "  {source} -> {destination};");
+            }
+        }
+        dot.push_str("}\n");
+        dot
     }
 }
 
+const MAX_COUNTER_PRESSES: usize = 1 << 16;
+
 impl AdventSolver for Advent2023Day20Solver {
     fn solve_part1(&self) -> usize {
         let mut configuration = self.new_configuration();
-        (0..1000).for_each(|_| configuration.push_button());
+        (0..1000).for_each(|_| {
+            configuration.push_button();
+        });
         configuration.low * configuration.high
     }
 
     fn solve_part2(&self) -> usize {
-        let mut configuration = self.new_configuration();
-        while configuration.rx_low_button_presses().is_none() {
-            configuration.push_button();
-        }
-        configuration.rx_low_button_presses().unwrap()
+        self.rx_low_button_presses()
+            .expect("cannot find when rx receives a low pulse")
     }
 }
 
 type ModuleName = String;
 
+/// Part of the network driven by a single broadcaster output, sending a high pulse to the sink
+/// on the presses `phases` and then every `period` presses after the first `offset` ones.
+#[derive(Debug, PartialEq, Eq)]
+struct SubCounter {
+    start: ModuleName,
+    output: ModuleName,
+    offset: usize,
+    period: usize,
+    phases: Vec<usize>,
+}
+
+#[derive(PartialEq, Eq)]
+pub enum NetworkError {
+    NoRx,
+    RxNotFedByConjunction,
+    DependentCounter(ModuleName),
+    NoPeriod(ModuleName),
+    NeverLow,
+}
+
+impl Debug for NetworkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NetworkError::NoRx => f.write_str("no module sends pulses to rx"),
+            NetworkError::RxNotFedByConjunction => {
+                f.write_str("rx is not fed by a single conjunction module")
+            }
+            NetworkError::DependentCounter(m) => f.write_fmt(format_args!(
+                "subgraph starting at {m} is not an independent counter"
+            )),
+            NetworkError::NoPeriod(m) => f.write_fmt(format_args!(
+                "subgraph starting at {m} does not repeat within {MAX_COUNTER_PRESSES} presses"
+            )),
+            NetworkError::NeverLow => f.write_str("counters never line up to send rx a low pulse"),
+        }
+    }
+}
+
 struct ModuleConfiguration {
     modules: HashMap<ModuleName, Box<dyn Module>>,
     low: usize,
     high: usize,
-    button_presses: usize,
-    rx_requirements: HashMap<String, Option<usize>>,
+    sink: Option<ModuleName>,
 }
 
 impl ModuleConfiguration {
-    fn new(modules: HashMap<ModuleName, Box<dyn Module>>, rx_requirements: Vec<String>) -> Self {
+    fn new(modules: HashMap<ModuleName, Box<dyn Module>>, sink: Option<ModuleName>) -> Self {
         Self {
             modules,
             low: 0,
             high: 0,
-            button_presses: 0,
-            rx_requirements: rx_requirements.into_iter().map(|r| (r, None)).collect(),
+            sink,
         }
     }
 
-    fn rx_low_button_presses(&self) -> Option<usize> {
-        if self.rx_requirements.values().all(|v| v.is_some()) {
-            Some(self.rx_requirements.values().fold(1, |a, c| a * c.unwrap()))
-        } else {
-            None
-        }
+    fn state(&self) -> Vec<bool> {
+        self.modules
+            .iter()
+            .sorted_by_key(|(n, _)| *n)
+            .flat_map(|(_, m)| m.state())
+            .collect()
     }
 
-    fn push_button(&mut self) {
-        self.button_presses += 1;
+    /// Returns whether a high pulse was sent to the sink during this press.
+    fn push_button(&mut self) -> bool {
+        let mut sink_high = false;
         let mut queue = VecDeque::new();
         queue.push_back(Pulse {
             from: String::from("button"),
@@ -142,12 +311,7 @@ impl ModuleConfiguration {
         while let Some(current) = queue.pop_front() {
             if current.high {
                 self.high += 1;
-                if let Some(v) = self.rx_requirements.get(&current.from) {
-                    if v.is_none() {
-                        self.rx_requirements
-                            .insert(current.from.clone(), Some(self.button_presses));
-                    }
-                }
+                sink_high |= self.sink.as_ref() == Some(&current.to);
             } else {
                 self.low += 1;
             }
@@ -155,11 +319,13 @@ impl ModuleConfiguration {
                 queue.extend(module.receive(current));
             }
         }
+        sink_high
     }
 }
 
 trait Module {
     fn receive(&mut self, pulse: Pulse) -> Vec<Pulse>;
+    fn state(&self) -> Vec<bool>;
 }
 
 struct Broadcaster {
@@ -179,6 +345,10 @@ impl Module for Broadcaster {
             .map(|d| pulse.next(pulse.high, d))
             .collect()
     }
+
+    fn state(&self) -> Vec<bool> {
+        vec![]
+    }
 }
 
 struct FlipFlop {
@@ -206,6 +376,10 @@ impl Module for FlipFlop {
             .map(|d| pulse.next(self.high, d))
             .collect()
     }
+
+    fn state(&self) -> Vec<bool> {
+        vec![self.high]
+    }
 }
 
 struct Conjunction {
@@ -231,6 +405,14 @@ impl Module for Conjunction {
             .map(|d| pulse.next(!high, d))
             .collect()
     }
+
+    fn state(&self) -> Vec<bool> {
+        self.lasts
+            .iter()
+            .sorted_by_key(|(n, _)| *n)
+            .map(|(_, &h)| h)
+            .collect()
+    }
 }
 
 struct Pulse {
@@ -277,4 +459,54 @@ broadcaster -> a
         let solver = Advent2023Day20Solver::new(EXAMPLE);
         assert_eq!(solver.solve_part1(), 11687500);
     }
+
+    const COUNTERS: &str = "\
+broadcaster -> a1, b1
+%a1 -> a2, ha
+%a2 -> ha
+&ha -> ia
+&ia -> hub
+%b1 -> b2, hb
+%b2 -> b3
+%b3 -> hb
+&hb -> ib
+&ib -> hub
+&hub -> rx
+";
+
+    #[test]
+    fn combines_counter_periods() {
+        let solver = Advent2023Day20Solver::new(COUNTERS);
+        let counters = solver.counters().unwrap();
+        assert_eq!(
+            counters
+                .iter()
+                .map(|c| (c.output.as_str(), c.offset, c.period, c.phases.clone()))
+                .collect_vec(),
+            vec![("ia", 1, 4, vec![3]), ("ib", 1, 8, vec![5, 7])]
+        );
+        assert_eq!(solver.solve_part2(), 7);
+        assert_eq!(
+            solver.counter_report().unwrap(),
+            "a1 -> ia: offset 1, period 4, phases [3]\nb1 -> ib: offset 1, period 8, phases [5, 7]\n"
+        );
+    }
+
+    #[test]
+    fn rejects_networks_without_counters() {
+        let solver = Advent2023Day20Solver::new(EXAMPLE);
+        assert_eq!(solver.rx_low_button_presses(), Err(NetworkError::NoRx));
+        let solver = Advent2023Day20Solver::new(&COUNTERS.replace("%b2 -> b3", "%b2 -> b3, a2"));
+        assert_eq!(
+            solver.rx_low_button_presses(),
+            Err(NetworkError::DependentCounter(String::from("a1")))
+        );
+    }
+
+    #[test]
+    fn exports_dot() {
+        let dot = Advent2023Day20Solver::new(EXAMPLE).to_dot();
+        assert!(dot.contains("inv [shape=diamond, label=\"&inv\"];"));
+        assert!(dot.contains("broadcaster -> a;"));
+    }
 }
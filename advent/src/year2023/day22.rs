@@ -1,12 +1,12 @@
 use std::collections::{HashSet, VecDeque};
 use std::fmt::{Debug, Formatter};
-use std::ops::RangeInclusive;
 
 use itertools::Itertools;
 use regex::{Match, Regex};
 
 use Falling::*;
 
+use crate::common::cuboid::Cuboid;
 use crate::solver::AdventSolver;
 
 pub struct Advent2023Day22Solver {
@@ -23,10 +23,9 @@ impl Advent2023Day22Solver {
                     .lines()
                     .filter_map(|l| re.captures(l))
                     .map(|cap| {
-                        (
-                            parse(cap.get(1))..=parse(cap.get(4)),
-                            parse(cap.get(2))..=parse(cap.get(5)),
-                            parse(cap.get(3))..=parse(cap.get(6)),
+                        Cuboid::new(
+                            [parse(cap.get(1)), parse(cap.get(2)), parse(cap.get(3))],
+                            [parse(cap.get(4)), parse(cap.get(5)), parse(cap.get(6))],
                         )
                     })
                     .collect(),
@@ -50,17 +49,17 @@ struct Stack {
 }
 
 impl Stack {
-    fn new(brick_defs: Vec<BrickDef>) -> Self {
+    fn new(brick_defs: Vec<Cuboid<3>>) -> Self {
         let mut below: Vec<Vec<usize>> = vec![];
         below.resize_with(brick_defs.len(), Vec::new);
         let mut above: Vec<Vec<usize>> = vec![];
         above.resize_with(brick_defs.len(), Vec::new);
         for i in 0..brick_defs.len() {
             for j in i + 1..brick_defs.len() {
-                if !overlaps(&brick_defs[i], &brick_defs[j]) {
+                if !brick_defs[i].extended(2).intersects(&brick_defs[j]) {
                     continue;
                 }
-                if brick_defs[i].2.start() < brick_defs[j].2.start() {
+                if brick_defs[i].min[2] < brick_defs[j].min[2] {
                     below[j].push(i);
                     above[i].push(j);
                 } else {
@@ -74,9 +73,7 @@ impl Stack {
         for i in 0..brick_defs.len() {
             bricks.push(Brick {
                 index: i,
-                x: brick_defs[i].0.clone(),
-                y: brick_defs[i].1.clone(),
-                z: brick_defs[i].2.clone(),
+                cuboid: brick_defs[i],
                 below: below[i].clone(),
                 above: above[i].clone(),
                 supporting: vec![],
@@ -97,10 +94,10 @@ impl Stack {
                 let min = brick
                     .below
                     .iter()
-                    .map(|&bi| *self.bricks[bi].z.end())
+                    .map(|&bi| self.bricks[bi].cuboid.max[2])
                     .max()
                     .unwrap_or(0);
-                if *brick.z.start() == min + 1 {
+                if brick.cuboid.min[2] == min + 1 {
                     continue;
                 }
                 self.bricks[i].lower_to(min + 1);
@@ -108,19 +105,19 @@ impl Stack {
             }
         }
         for i in 0..self.bricks.len() {
-            let z_start = *self.bricks[i].z.start();
+            let z_start = self.bricks[i].cuboid.min[2];
             self.bricks[i].supported_by = self.bricks[i]
                 .below
                 .iter()
-                .filter(|&&b| *self.bricks[b].z.end() + 1 == z_start)
+                .filter(|&&b| self.bricks[b].cuboid.max[2] + 1 == z_start)
                 .cloned()
                 .collect();
 
-            let z_end = *self.bricks[i].z.end();
+            let z_end = self.bricks[i].cuboid.max[2];
             self.bricks[i].supporting = self.bricks[i]
                 .above
                 .iter()
-                .filter(|&&a| *self.bricks[a].z.start() == z_end + 1)
+                .filter(|&&a| self.bricks[a].cuboid.min[2] == z_end + 1)
                 .cloned()
                 .collect();
         }
@@ -148,7 +145,7 @@ impl Stack {
         let mut queue: VecDeque<usize> = self
             .bricks
             .iter()
-            .sorted_by_key(|b| b.z.start())
+            .sorted_by_key(|b| b.cuboid.min[2])
             .rev()
             .map(|b| b.index)
             .collect();
@@ -258,9 +255,7 @@ impl Debug for Falling {
 #[derive(Clone)]
 struct Brick {
     index: usize,
-    x: RangeInclusive<usize>,
-    y: RangeInclusive<usize>,
-    z: RangeInclusive<usize>,
+    cuboid: Cuboid<3>,
     supporting: Vec<usize>,
     supported_by: Vec<usize>,
     below: Vec<usize>,
@@ -268,8 +263,8 @@ struct Brick {
 }
 
 impl Brick {
-    fn lower_to(&mut self, min: usize) {
-        self.z = min..=(self.z.end() - self.z.start() + min);
+    fn lower_to(&mut self, min: i64) {
+        self.cuboid = self.cuboid.translate([0, 0, min - self.cuboid.min[2]]);
     }
 }
 
@@ -278,12 +273,12 @@ impl Debug for Brick {
         f.write_fmt(format_args!(
             "{:3}= {},{},{}~{},{},{}  below = ({} -> {}) + above = ({} -> {})",
             self.index,
-            self.x.start(),
-            self.y.start(),
-            self.z.start(),
-            self.x.end(),
-            self.y.end(),
-            self.z.end(),
+            self.cuboid.min[0],
+            self.cuboid.min[1],
+            self.cuboid.min[2],
+            self.cuboid.max[0],
+            self.cuboid.max[1],
+            self.cuboid.max[2],
             self.below.iter().join(","),
             self.supported_by.iter().join(","),
             self.above.iter().join(","),
@@ -292,19 +287,6 @@ impl Debug for Brick {
     }
 }
 
-type BrickDef = (
-    RangeInclusive<usize>,
-    RangeInclusive<usize>,
-    RangeInclusive<usize>,
-);
-
-fn overlaps(a: &BrickDef, b: &BrickDef) -> bool {
-    a.0.start() <= b.0.end()
-        && a.0.end() >= b.0.start()
-        && a.1.start() <= b.1.end()
-        && a.1.end() >= b.1.start()
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,16 +1,20 @@
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter};
-use std::ops::{Add, Mul, Range, Sub};
+use std::ops::RangeInclusive;
 
 use itertools::Itertools;
 use num_integer::sqrt;
 use num_traits::abs;
 use regex::{Match, Regex};
 
+use crate::common::linear::{LinearSolution, solve_linear_system};
+use crate::common::rational::Rational;
+use crate::common::vec3::Vec3;
 use crate::solver::AdventSolver;
 
 pub struct Advent2023Day24Solver {
     hailstorm: Hailstorm,
+    test_area: RangeInclusive<i64>,
 }
 
 impl Advent2023Day24Solver {
@@ -24,12 +28,12 @@ impl Advent2023Day24Solver {
                     .filter_map(|l| re.captures(l))
                     .map(|cap| {
                         Hailstone::new(
-                            Int3 {
+                            Vec3 {
                                 x: parse(cap.get(1)),
                                 y: parse(cap.get(2)),
                                 z: parse(cap.get(3)),
                             },
-                            Int3 {
+                            Vec3 {
                                 x: parse(cap.get(4)),
                                 y: parse(cap.get(5)),
                                 z: parse(cap.get(6)),
@@ -38,16 +42,19 @@ impl Advent2023Day24Solver {
                     })
                     .collect(),
             },
+            test_area: 200000000000000..=400000000000000,
         }
     }
+
+    /// Counts part 1 crossings within `test_area` instead of the puzzle's default area.
+    pub fn with_test_area(self, test_area: RangeInclusive<i64>) -> Self {
+        Self { test_area, ..self }
+    }
 }
 
 impl AdventSolver for Advent2023Day24Solver {
     fn solve_part1(&self) -> usize {
-        self.hailstorm.crossing_count(Range2 {
-            x: 200000000000000f64..400000000000000f64,
-            y: 200000000000000f64..400000000000000f64,
-        })
+        self.hailstorm.crossing_count(&self.test_area)
     }
 
     fn solve_part2(&self) -> usize {
@@ -64,14 +71,18 @@ struct Hailstorm {
 }
 
 impl Hailstorm {
-    fn crossing_count(&self, range: Range2) -> usize {
+    fn crossing_count(&self, area: &RangeInclusive<i64>) -> usize {
         let mut count = 0;
         for i in 0..self.hailstones.len() {
             for j in i + 1..self.hailstones.len() {
                 if let Some(intersection) =
                     self.hailstones[i].future_intersection_with(&self.hailstones[j])
                 {
-                    if range.contains(intersection) {
+                    let inside = |v: &Rational| {
+                        *v >= Rational::from(*area.start() as i128)
+                            && *v <= Rational::from(*area.end() as i128)
+                    };
+                    if inside(&intersection[0]) && inside(&intersection[1]) {
                         count += 1;
                     }
                 }
@@ -138,12 +149,12 @@ impl Hailstorm {
                 for &vz in &z_velocities {
                     let pz = other.position.z + time * (other.velocity.z - vz);
                     let rock = Hailstone::new(
-                        Int3 {
+                        Vec3 {
                             x: x_match.position.x,
                             y: py,
                             z: pz,
                         },
-                        Int3 {
+                        Vec3 {
                             x: x_match.velocity.x,
                             y: vy,
                             z: vz,
@@ -167,12 +178,12 @@ impl Hailstorm {
                 for &vz in &z_velocities {
                     let pz = other.position.z + time * (other.velocity.z - vz);
                     let rock = Hailstone::new(
-                        Int3 {
+                        Vec3 {
                             x: px,
                             y: y_match.position.y,
                             z: pz,
                         },
-                        Int3 {
+                        Vec3 {
                             x: vx,
                             y: y_match.velocity.y,
                             z: vz,
@@ -196,12 +207,12 @@ impl Hailstorm {
                 for &vy in &y_velocities {
                     let py = other.position.y + time * (other.velocity.y - vy);
                     let rock = Hailstone::new(
-                        Int3 {
+                        Vec3 {
                             x: px,
                             y: py,
                             z: z_match.position.z,
                         },
-                        Int3 {
+                        Vec3 {
                             x: vx,
                             y: vy,
                             z: z_match.velocity.z,
@@ -219,53 +230,35 @@ impl Hailstorm {
 
 #[derive(Clone, Eq, PartialEq)]
 struct Hailstone {
-    position: Int3,
-    velocity: Int3,
+    position: Vec3,
+    velocity: Vec3,
 }
 
 impl Hailstone {
-    fn new(position: Int3, velocity: Int3) -> Self {
+    fn new(position: Vec3, velocity: Vec3) -> Self {
         Self { position, velocity }
     }
 
-    fn future_intersection_with(&self, other: &Self) -> Option<Float2> {
-        let p1 = self.as_float();
-        let p2 = self.next();
-        let p3 = other.as_float();
-        let p4 = other.next();
-        let den = (p1.x - p2.x) * (p3.y - p4.y) - (p1.y - p2.y) * (p3.x - p4.x);
-        if den == 0f64 {
+    fn future_intersection_with(&self, other: &Self) -> Option<[Rational; 2]> {
+        let r = |v: i64| Rational::from(v as i128);
+        let a = vec![
+            vec![r(self.velocity.x), r(-other.velocity.x)],
+            vec![r(self.velocity.y), r(-other.velocity.y)],
+        ];
+        let b = [
+            r(other.position.x - self.position.x),
+            r(other.position.y - self.position.y),
+        ];
+        let LinearSolution::Unique(times) = solve_linear_system(&a, &b) else {
+            return None;
+        };
+        if times.iter().any(Rational::is_negative) {
             return None;
         }
-        let n1 = p1.x * p2.y - p1.y * p2.x;
-        let n2 = p3.x * p4.y - p3.y * p4.x;
-        let nx = n1 * (p3.x - p4.x) - n2 * (p1.x - p2.x);
-        let ny = n1 * (p3.y - p4.y) - n2 * (p1.y - p2.y);
-        let x = nx / den;
-        let y = ny / den;
-        if (self.velocity.x > 0 && x < p1.x)
-            || (self.velocity.x < 0 && x > p1.x)
-            || (other.velocity.x > 0 && x < p3.x)
-            || (other.velocity.x < 0 && x > p3.x)
-        {
-            None
-        } else {
-            Some(Float2 { x, y })
-        }
-    }
-
-    fn as_float(&self) -> Float2 {
-        Float2 {
-            x: self.position.x as f64,
-            y: self.position.y as f64,
-        }
-    }
-
-    fn next(&self) -> Float2 {
-        Float2 {
-            x: (self.position.x + self.velocity.x) as f64,
-            y: (self.position.y + self.velocity.y) as f64,
-        }
+        Some([
+            r(self.position.x) + times[0].clone() * r(self.velocity.x),
+            r(self.position.y) + times[0].clone() * r(self.velocity.y),
+        ])
     }
 
     fn hits_all(&self, others: &[Self]) -> bool {
@@ -330,72 +323,6 @@ impl Parallel {
     }
 }
 
-struct Range2 {
-    x: Range<f64>,
-    y: Range<f64>,
-}
-
-impl Range2 {
-    fn contains(&self, float: Float2) -> bool {
-        self.x.contains(&float.x) && self.y.contains(&float.y)
-    }
-}
-
-#[derive(Copy, Clone)]
-struct Float2 {
-    x: f64,
-    y: f64,
-}
-
-#[derive(Copy, Clone, Eq, PartialEq)]
-struct Int3 {
-    x: i64,
-    y: i64,
-    z: i64,
-}
-
-impl Debug for Int3 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{:3}, {:3}, {:3}", self.x, self.y, self.z))
-    }
-}
-
-impl Mul<i64> for Int3 {
-    type Output = Self;
-
-    fn mul(self, rhs: i64) -> Self::Output {
-        Self {
-            x: self.x * rhs,
-            y: self.y * rhs,
-            z: self.z * rhs,
-        }
-    }
-}
-
-impl Add for Int3 {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-            z: self.z + rhs.z,
-        }
-    }
-}
-
-impl Sub for Int3 {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z,
-        }
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -410,12 +337,8 @@ mod test {
 
     #[test]
     fn counts_future_hailstone_crossings() {
-        let solver = Advent2023Day24Solver::new(EXAMPLE);
-        let solution = solver.hailstorm.crossing_count(Range2 {
-            x: 7f64..27f64,
-            y: 7f64..27f64,
-        });
-        assert_eq!(solution, 2);
+        let solver = Advent2023Day24Solver::new(EXAMPLE).with_test_area(7..=27);
+        assert_eq!(solver.solve_part1(), 2);
     }
 
     #[test]
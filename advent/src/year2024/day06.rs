@@ -1,3 +1,4 @@
+use crate::common::parallel;
 use crate::solver::AdventSolver;
 use itertools::Itertools;
 use std::collections::HashSet;
@@ -43,21 +44,24 @@ impl AdventSolver for Advent2024Day06Solver {
     }
 
     fn solve_part2(&self) -> usize {
-        // todo slow (12s)
+        // todo slow (12s), unless built with the parallel feature
         let mut guard = self.grid.new_guard();
         guard.patrol(&self.grid);
-        guard
+        let candidates = guard
             .seen
             .iter()
             .map(|&(pos, _)| pos)
             .unique()
-            .filter(|&p| {
-                let new_grid = self.grid.add_obstacle(p);
-                let mut new_guard = new_grid.new_guard();
-                new_guard.patrol(&new_grid);
-                new_guard.looped
-            })
-            .count()
+            .collect_vec();
+        parallel::map(&candidates, |&p| {
+            let new_grid = self.grid.add_obstacle(p);
+            let mut new_guard = new_grid.new_guard();
+            new_guard.patrol(&new_grid);
+            new_guard.looped
+        })
+        .into_iter()
+        .filter(|&looped| looped)
+        .count()
     }
 }
 
@@ -1,4 +1,6 @@
 use itertools::Itertools;
+use crate::common::linear::{integer_solution, solve_linear_system, LinearSolution};
+use crate::common::rational::Rational;
 use crate::solver::AdventSolver;
 use regex::{Match, Regex};
 
@@ -66,34 +68,20 @@ impl ClawMachine {
     }
 
     fn solution(&self) -> Option<(usize, usize)> {
-        // px = ax * a + bx * b
-        // py = ay * a + by * b
-        // by * b = py - ay * a
-        // b = (py - ay * a) / by
-        // px = ax * a + bx * (py - ay * a) / by
-        // by * px = by * ax * a + bx * (py - ay * a)
-        // by * px = by * ax * a + bx * py - bx * ay * a
-        // bx * ay * a - by * ax * a = bx * py - by * px
-        // a * ( bx * ay - by * ax ) = (bx * py - by * px)
-        // a = (bx_py - by_px) / (ay_bx - ax_by)
-        let bx_py = self.button_b.x * self.prize.y;
-        let by_px = self.button_b.y * self.prize.x;
-        let ax_by = self.button_a.x * self.button_b.y;
-        let ay_bx = self.button_a.y * self.button_b.x;
-        if by_px > bx_py && ax_by < ay_bx {
+        let r = |v: usize| Rational::from(v as i128);
+        let a = vec![
+            vec![r(self.button_a.x), r(self.button_b.x)],
+            vec![r(self.button_a.y), r(self.button_b.y)],
+        ];
+        let LinearSolution::Unique(presses) =
+            solve_linear_system(&a, &[r(self.prize.x), r(self.prize.y)])
+        else {
             return None;
+        };
+        match integer_solution(&presses)?[..] {
+            [a, b] if a >= 0 && b >= 0 => Some((a as usize, b as usize)),
+            _ => None,
         }
-        let r = bx_py.abs_diff(by_px);
-        let l = ax_by.abs_diff(ay_bx);
-        if r % l != 0 {
-            return None;
-        }
-        let a = r / l;
-        let b = self.prize.y - self.button_a.y * a;
-        if b % self.button_b.y != 0 {
-            return None;
-        }
-        Some((a, b / self.button_b.y))
     }
 }
 
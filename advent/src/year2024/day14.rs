@@ -1,5 +1,7 @@
 use std::cmp::Ordering;
+use crate::common::modular::crt;
 use crate::solver::AdventSolver;
+use crate::visualize::{BLACK, Frame, FrameSink, GREEN, Visualize};
 use regex::{Match, Regex};
 use std::fmt::{Debug, Formatter};
 
@@ -26,6 +28,14 @@ impl Advent2024Day14Solver {
             ),
         }
     }
+
+    /// The robots at the second they draw the picture, to check the part 2 answer by eye.
+    pub fn picture(&self) -> String {
+        self.bathroom
+            .find_picture(true)
+            .and_then(|picture| picture.drawing)
+            .expect("robots never gather on both axes at once")
+    }
 }
 
 impl AdventSolver for Advent2024Day14Solver {
@@ -38,13 +48,28 @@ impl AdventSolver for Advent2024Day14Solver {
     }
 
     fn solve_part2(&self) -> usize {
+        self.bathroom
+            .find_picture(false)
+            .expect("robots never gather on both axes at once")
+            .second
+    }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
+}
+
+impl Visualize for Advent2024Day14Solver {
+    /// Moves the robots second by second until they draw the tree.
+    fn visualize(&self, sink: &mut dyn FrameSink) -> std::io::Result<()> {
+        let second = self.bathroom.find_picture(false).map_or(0, |p| p.second);
         let mut bathroom = self.bathroom.clone();
-        let mut i = 0;
-        while !bathroom.is_tree_like() {
+        sink.frame(&bathroom.frame())?;
+        for _ in 0..second {
             bathroom.iterate();
-            i += 1;
+            sink.frame(&bathroom.frame())?;
         }
-        i
+        Ok(())
     }
 }
 
@@ -94,21 +119,57 @@ impl Bathroom {
         quadrant_counts.iter().product()
     }
 
-    fn is_tree_like(&self) -> bool {
-        let mut rows = vec![0; self.width as usize];
-        let mut cols = vec![0; self.height as usize];
-        let threshold = self.width as usize / 3;
+    fn frame(&self) -> Frame {
+        let mut frame = Frame::new(self.width as usize, self.height as usize, BLACK);
         self.robots
             .iter()
-            .for_each(|r| {
-                rows[r.position.0 as usize] += 1;
-                cols[r.position.1 as usize] += 1;
-            });
-        rows.iter().any(|&r| r > threshold) &&
-            cols.iter().any(|&r| r > threshold)
+            .for_each(|r| frame.set(r.position.0 as usize, r.position.1 as usize, GREEN));
+        frame
+    }
+
+    /// Finds the first second where the robots gather into a picture.
+    ///
+    /// Horizontal positions repeat every `width` seconds and vertical ones every `height`
+    /// seconds, so the picture shows up at the second of least horizontal spread within the
+    /// first `width` seconds, combined through the CRT with the second of least vertical
+    /// spread within the first `height` seconds.
+    fn find_picture(&self, render: bool) -> Option<Picture> {
+        let spread = |period: i32, axis: fn(Pos) -> i32| {
+            (0..period)
+                .min_by_key(|&t| {
+                    let values = self
+                        .robots
+                        .iter()
+                        .map(|r| axis(r.position_at(t, self.width, self.height)) as i64);
+                    let (sum, sum_squares) =
+                        values.fold((0, 0), |(s, q), v| (s + v, q + v * v));
+                    // variance scaled by the squared robot count
+                    self.robots.len() as i64 * sum_squares - sum * sum
+                })
+                .unwrap_or(0)
+        };
+        let x = spread(self.width, |p| p.0);
+        let y = spread(self.height, |p| p.1);
+        let (second, _) = crt([
+            (x as i128, self.width as i128),
+            (y as i128, self.height as i128),
+        ])?;
+        let second = second as usize;
+        let drawing = render.then(|| {
+            let mut bathroom = self.clone();
+            (0..second).for_each(|_| bathroom.iterate());
+            format!("{:?}", bathroom)
+        });
+        Some(Picture { second, drawing })
     }
 }
 
+struct Picture {
+    second: usize,
+    // only rendered when asked for, as it replays every second up to the picture
+    drawing: Option<String>,
+}
+
 impl Debug for Bathroom {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut board = String::with_capacity(self.width as usize * (self.height as usize + 2));
@@ -137,6 +198,13 @@ impl Robot {
         Self { position, velocity }
     }
 
+    fn position_at(&self, seconds: i32, width: i32, height: i32) -> Pos {
+        (
+            (self.position.0 + self.velocity.0 * seconds).rem_euclid(width),
+            (self.position.1 + self.velocity.1 * seconds).rem_euclid(height),
+        )
+    }
+
     fn iterate(&mut self, width: i32, height: i32) {
         self.position.0 += self.velocity.0 + width;
         self.position.0 %= width;
@@ -171,4 +239,27 @@ p=9,5 v=-3,-3
         solver.bathroom.height = 7;
         assert_eq!(solver.solve_part1(), 12);
     }
+
+    #[test]
+    fn finds_picture_with_least_spread() {
+        let mut solver = Advent2024Day14Solver::new(EXAMPLE);
+        solver.bathroom.width = 11;
+        solver.bathroom.height = 7;
+        // send every robot to the 3x3 square around (5, 3) after 40 seconds
+        solver.bathroom.robots.iter_mut().enumerate().for_each(|(i, r)| {
+            let target = (4 + i as i32 % 3, 2 + i as i32 / 4 % 3);
+            r.position = (
+                (target.0 - r.velocity.0 * 40).rem_euclid(11),
+                (target.1 - r.velocity.1 * 40).rem_euclid(7),
+            );
+        });
+        let picture = solver.bathroom.find_picture(true).unwrap();
+        assert_eq!(picture.second, 40);
+        assert_eq!(solver.solve_part2(), 40);
+        let drawing = picture.drawing.unwrap();
+        assert_eq!(drawing.lines().nth(2), Some("           "));
+        assert_eq!(drawing.lines().nth(3), Some("    ***    "));
+        assert_eq!(solver.solve_part2_string(), "40");
+        assert_eq!(solver.picture(), drawing);
+    }
 }
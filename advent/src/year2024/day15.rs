@@ -1,4 +1,5 @@
 use crate::solver::AdventSolver;
+use crate::visualize::{BLACK, BROWN, Frame, FrameSink, GRAY, GREEN, Visualize};
 use itertools::Itertools;
 use std::fmt::{Debug, Formatter};
 use MapTile::*;
@@ -40,6 +41,23 @@ impl AdventSolver for Advent2024Day15Solver {
         self.movements.iter().for_each(|&m| warehouse.execute(m));
         warehouse.boxes().iter().map(|(y, x)| y * 100 + x).sum()
     }
+
+    fn visualizer(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
+}
+
+impl Visualize for Advent2024Day15Solver {
+    /// Runs the robot through the widened warehouse, one frame per movement.
+    fn visualize(&self, sink: &mut dyn FrameSink) -> std::io::Result<()> {
+        let mut warehouse = self.warehouse.widen();
+        sink.frame(&warehouse.frame())?;
+        for &movement in &self.movements {
+            warehouse.execute(movement);
+            sink.frame(&warehouse.frame())?;
+        }
+        Ok(())
+    }
 }
 
 type Pos = (usize, usize);
@@ -255,6 +273,17 @@ impl Warehouse {
         }
     }
 
+    fn frame(&self) -> Frame {
+        Frame::from_fn(self.map[0].len(), self.map.len(), |x, y| match self.tile((y, x)) {
+            Wall => GRAY,
+            Empty => BLACK,
+            Box | BoxLeft => BROWN,
+            // darker right halves keep neighbouring wide boxes apart
+            BoxRight => [110, 70, 30],
+            Robot => GREEN,
+        })
+    }
+
     fn boxes(&self) -> Vec<Pos> {
         self.map
             .iter()
@@ -1,3 +1,4 @@
+use crate::common::parallel;
 use crate::solver::AdventSolver;
 use itertools::Itertools;
 use std::collections::{HashMap, VecDeque};
@@ -16,24 +17,36 @@ impl Advent2024Day22Solver {
 
 impl AdventSolver for Advent2024Day22Solver {
     fn solve_part1(&self) -> usize {
-        self.buyers
-            .iter()
-            .map(|buyer| buyer.secret_numbers().nth(2000).unwrap())
-            .sum()
+        parallel::map(&self.buyers, |buyer| {
+            buyer.secret_numbers().nth(2000).unwrap()
+        })
+        .into_iter()
+        .sum()
     }
 
     fn solve_part2(&self) -> usize {
-        let mut sequence_sums = HashMap::new();
-        self.buyers
-            .iter()
-            .flat_map(|buyer| buyer.sequences())
-            .for_each(|(sequence, value)| {
-                sequence_sums
-                    .entry(sequence)
+        // each worker sums its buyers as it goes, rather than keeping every buyer's map around
+        let add_all = |sums: &mut HashMap<_, usize>, sequences: HashMap<_, usize>| {
+            sequences.into_iter().for_each(|(sequence, value)| {
+                sums.entry(sequence)
                     .and_modify(|v| *v += value)
                     .or_insert(value);
             });
-        sequence_sums.into_values().max().unwrap()
+        };
+        parallel::map_chunks(&self.buyers, |buyers| {
+            let mut sums = HashMap::new();
+            buyers
+                .iter()
+                .for_each(|buyer| add_all(&mut sums, buyer.sequences()));
+            sums
+        })
+        .into_iter()
+        .reduce(|mut sequence_sums, sums| {
+            add_all(&mut sequence_sums, sums);
+            sequence_sums
+        })
+        .and_then(|sequence_sums| sequence_sums.into_values().max())
+        .unwrap()
     }
 }
 
@@ -1,4 +1,7 @@
-use itertools::Itertools;
+use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
+
+use crate::common::intervals::IntervalSet;
 use crate::solver::AdventSolver;
 
 pub struct Advent2025Day02Solver {
@@ -10,10 +13,9 @@ impl Advent2025Day02Solver {
         Self {
             product_ranges: input
                 .split(',')
-                .map(|r| r.split('-'))
-                .map(|mut s| ProductRange {
-                    first: s.next().unwrap().parse::<usize>().unwrap(),
-                    last: s.next().unwrap().parse::<usize>().unwrap(),
+                .map(|r| r.split_once('-').unwrap())
+                .map(|(first, last)| ProductRange {
+                    ids: IntervalSet::from(first.parse().unwrap()..=last.parse().unwrap()),
                 })
                 .collect(),
         }
@@ -37,56 +39,38 @@ impl AdventSolver for Advent2025Day02Solver {
 }
 
 struct ProductRange {
-    first: usize,
-    last: usize,
+    ids: IntervalSet<usize>,
 }
 
 impl ProductRange {
-    fn half_invalid_ids(&self) -> impl Iterator<Item = usize> {
-        (self.first..=self.last).filter(is_half_invalid)
+    fn half_invalid_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.repeated_ids(|_| 2..=2)
     }
 
-    fn full_invalid_ids(&self) -> impl Iterator<Item = usize> {
-        (self.first..=self.last).filter(is_full_invalid)
+    fn full_invalid_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.repeated_ids(|digits| 2..=digits)
     }
-}
-
-fn is_half_invalid(id: &usize) -> bool {
-    let full_size = id.ilog10();
-    if full_size % 2 == 0 {
-        return false;
-    }
-    let half_size = full_size / 2 + 1;
-    let half_power = 10usize.pow(half_size);
-    let first_half = id / half_power;
-    let second_half = id % half_power;
-    first_half == second_half
-}
-
-fn is_full_invalid(id: &usize) -> bool {
-    let mut size = 10;
-    while size < *id {
-        if is_size_invalid(id, size) {
-            return true;
-        }
-        size *= 10;
-    }
-    false
-}
 
-fn is_size_invalid(id: &usize, size: usize) -> bool {
-    let mut splits = Vec::new();
-    let mut id = *id;
-    let expected_size = size.ilog10() - 1;
-    while id > 0 {
-        let split = id % size;
-        if split == 0 || split.ilog10() != expected_size {
-            return false;
+    fn repeated_ids(
+        &self,
+        repetitions: impl Fn(u32) -> RangeInclusive<u32>,
+    ) -> impl Iterator<Item = usize> + '_ {
+        let min_digits = self.ids.min().unwrap().max(1).ilog10() + 1;
+        let max_digits = self.ids.max().unwrap().max(1).ilog10() + 1;
+        let mut ids = BTreeSet::new();
+        for digits in min_digits..=max_digits {
+            for count in repetitions(digits).filter(|c| digits % c == 0) {
+                let pattern_size = digits / count;
+                let multiplier: usize = (0..count).map(|i| 10usize.pow(i * pattern_size)).sum();
+                ids.extend(
+                    (10usize.pow(pattern_size - 1)..10usize.pow(pattern_size))
+                        .map(|pattern| pattern * multiplier)
+                        .filter(|id| self.ids.contains(id)),
+                );
+            }
         }
-        splits.push(split);
-        id /= size;
+        ids.into_iter()
     }
-    splits.iter().all_equal()
 }
 
 #[cfg(test)]
@@ -0,0 +1,283 @@
+use integers::modular::inverse_mod;
+
+/// Permutation number `rank` of `items` in lexicographic order, counting from 0, read off the
+/// digits of `rank` in the factorial number system. `items` must be sorted and distinct.
+pub fn nth_permutation<T: Clone>(items: &[T], rank: u128) -> Option<Vec<T>> {
+    if factorial(items.len() as u64).is_some_and(|count| rank >= count) { return None }
+    let mut left = items.to_vec();
+    let mut rank = rank;
+    let mut permutation = Vec::with_capacity(items.len());
+    for i in (0..items.len()).rev() {
+        // a block of more than u128::MAX permutations always holds the rank, the digit is 0
+        let Some(block) = factorial(i as u64) else {
+            permutation.push(left.remove(0));
+            continue;
+        };
+        permutation.push(left.remove((rank / block) as usize));
+        rank %= block;
+    }
+    Some(permutation)
+}
+
+/// Position of `permutation` among the lexicographic permutations of its distinct elements,
+/// the inverse of [`nth_permutation`].
+pub fn permutation_rank<T: Ord>(permutation: &[T]) -> Option<u128> {
+    let n = permutation.len();
+    (0..n).try_fold(0u128, |rank, i| {
+        let smaller_after = permutation[i + 1..].iter().filter(|x| **x < permutation[i]).count() as u128;
+        if smaller_after == 0 { return Some(rank) }
+        rank.checked_add(smaller_after.checked_mul(factorial((n - i - 1) as u64)?)?)
+    })
+}
+
+/// Rearranges `items` into the next lexicographic permutation, false once they were the last.
+pub fn next_permutation<T: Ord>(items: &mut [T]) -> bool {
+    let Some(pivot) = (1..items.len()).rev().find(|&i| items[i - 1] < items[i]) else { return false };
+    let successor = (pivot..items.len()).rev().find(|&i| items[i] > items[pivot - 1]).unwrap();
+    items.swap(pivot - 1, successor);
+    items[pivot..].reverse();
+    true
+}
+
+/// Combination number `rank` of k elements out of 0..n, in lexicographic order from 0.
+pub fn nth_combination(n: u64, k: u64, rank: u128) -> Option<Vec<u64>> {
+    if k > n || rank >= binomial(n, k)? { return None }
+    let mut rank = rank;
+    let mut combination = Vec::with_capacity(k as usize);
+    let mut next = 0;
+    for left in (1..=k).rev() {
+        // skip every combination starting with a smaller element
+        loop {
+            let starting_here = binomial(n - next - 1, left - 1)?;
+            if rank < starting_here { break; }
+            rank -= starting_here;
+            next += 1;
+        }
+        combination.push(next);
+        next += 1;
+    }
+    Some(combination)
+}
+
+/// Lexicographic rank of an increasing combination of elements of 0..n, the inverse of
+/// [`nth_combination`].
+pub fn combination_rank(combination: &[u64], n: u64) -> Option<u128> {
+    let k = combination.len() as u64;
+    let mut rank = 0u128;
+    let mut previous = 0;
+    for (i, &c) in combination.iter().enumerate() {
+        let left = k - i as u64 - 1;
+        for skipped in previous..c {
+            rank = rank.checked_add(binomial(n - skipped - 1, left)?)?;
+        }
+        previous = c + 1;
+    }
+    Some(rank)
+}
+
+pub fn factorial(n: u64) -> Option<u128> {
+    (2..=n as u128).try_fold(1u128, |product, i| product.checked_mul(i))
+}
+
+/// n choose k, `None` when it does not fit in a u128. Intermediate products stay exact
+/// multiples of the result, so they only overflow when the result nearly does.
+pub fn binomial(n: u64, k: u64) -> Option<u128> {
+    if k > n { return Some(0) }
+    let k = k.min(n - k) as u128;
+    let n = n as u128;
+    (0..k).try_fold(1u128, |c, i| {
+        // c * (n - i) / (i + 1) is exact, divide what can be divided first
+        let g = gcd(c, i + 1);
+        let (c, d) = (c / g, (i + 1) / g);
+        c.checked_mul((n - i) / d)
+    })
+}
+
+/// Ways to split sum(counts) items into groups of the given sizes.
+pub fn multinomial(counts: &[u64]) -> Option<u128> {
+    let mut total = 0;
+    counts.iter().try_fold(1u128, |product, &count| {
+        total += count;
+        product.checked_mul(binomial(total, count)?)
+    })
+}
+
+/// First `rows` rows of Pascal's triangle, entries too large for a u128 saturating.
+pub fn pascal_triangle(rows: usize) -> Vec<Vec<u128>> {
+    let mut triangle: Vec<Vec<u128>> = Vec::with_capacity(rows);
+    for n in 0..rows {
+        let row = (0..=n)
+            .map(|k| if k == 0 || k == n { 1 } else { triangle[n - 1][k - 1].saturating_add(triangle[n - 1][k]) })
+            .collect();
+        triangle.push(row);
+    }
+    triangle
+}
+
+/// Binomial coefficients modulo a prime, from tables of factorials and their inverses.
+pub struct BinomialTable {
+    modulus: u64,
+    factorials: Vec<u64>,
+    inverse_factorials: Vec<u64>,
+}
+
+impl BinomialTable {
+    /// Covers n up to `max`, which must be smaller than the prime `modulus`.
+    pub fn new(max: usize, modulus: u64) -> Self {
+        assert!((max as u64) < modulus, "factorials would vanish modulo {modulus}");
+        let m = modulus as u128;
+        let mut factorials = vec!(1 % modulus; max + 1);
+        for i in 1..=max {
+            factorials[i] = (factorials[i - 1] as u128 * i as u128 % m) as u64;
+        }
+        let mut inverse_factorials = vec!(0; max + 1);
+        inverse_factorials[max] = inverse_mod(factorials[max] as u128, m).expect("modulus is not prime") as u64;
+        for i in (1..=max).rev() {
+            inverse_factorials[i - 1] = (inverse_factorials[i] as u128 * i as u128 % m) as u64;
+        }
+        Self { modulus, factorials, inverse_factorials }
+    }
+
+    pub fn binomial(&self, n: usize, k: usize) -> u64 {
+        if k > n { return 0 }
+        let m = self.modulus as u128;
+        let product = self.factorials[n] as u128 * self.inverse_factorials[k] as u128 % m;
+        (product * self.inverse_factorials[n - k] as u128 % m) as u64
+    }
+}
+
+/// Unsigned Stirling number of the first kind, permutations of n elements with k cycles.
+pub fn stirling_first(n: usize, k: usize) -> Option<u128> {
+    stirling(n, k, |n, _| n as u128)
+}
+
+/// Stirling number of the second kind, partitions of n elements into k non-empty sets.
+pub fn stirling_second(n: usize, k: usize) -> Option<u128> {
+    stirling(n, k, |_, k| k as u128)
+}
+
+/// Both kinds follow s(n + 1, k) = s(n, k - 1) + factor(n, k) * s(n, k), computed a row at a
+/// time. Only entries s(i, j) with i - j <= n - k lead to s(n, k), and none of them is larger,
+/// so any overflow among them means s(n, k) overflows too.
+fn stirling(n: usize, k: usize, factor: impl Fn(usize, usize) -> u128) -> Option<u128> {
+    if k > n { return Some(0) }
+    let mut row = vec!(0u128; k + 1);
+    row[0] = 1;
+    for i in 0..n {
+        for j in ((i + 1 + k).saturating_sub(n).max(1)..=k).rev() {
+            row[j] = factor(i, j).checked_mul(row[j])?.checked_add(row[j - 1])?;
+        }
+        row[0] = 0;
+    }
+    Some(row[k])
+}
+
+/// n-th Catalan number, binomial(2n, n) / (n + 1).
+pub fn catalan(n: u64) -> Option<u128> {
+    // C(n + 1) = C(n) * 2(2n + 1) / (n + 2), reduced first like binomial
+    (0..n as u128).try_fold(1u128, |c, i| {
+        let (a, b) = (2 * (2 * i + 1), i + 2);
+        let g = gcd(c, b);
+        let (c, b) = (c / g, b / g);
+        c.checked_mul(a / b)
+    })
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ranks_permutations() {
+        let items = ['a', 'b', 'c', 'd'];
+        let mut permutation = items;
+        for rank in 0..24 {
+            assert_eq!(nth_permutation(&items, rank), Some(permutation.to_vec()));
+            assert_eq!(permutation_rank(&permutation), Some(rank));
+            assert_eq!(next_permutation(&mut permutation), rank < 23);
+        }
+        assert_eq!(nth_permutation(&items, 24), None);
+        let digits = (0..10).collect::<Vec<u8>>();
+        assert_eq!(nth_permutation(&digits, 999_999), Some(vec!(2, 7, 8, 3, 9, 1, 5, 4, 6, 0)));
+    }
+
+    #[test]
+    fn ranks_permutations_of_more_items_than_factorials_reach() {
+        let items = (0..40).collect::<Vec<u8>>();
+        assert_eq!(nth_permutation(&items, 0), Some(items.clone()));
+        assert_eq!(permutation_rank(&items), Some(0));
+        let mut last_digits_swapped = items.clone();
+        last_digits_swapped.swap(38, 39);
+        assert_eq!(nth_permutation(&items, 1), Some(last_digits_swapped.clone()));
+        assert_eq!(permutation_rank(&last_digits_swapped), Some(1));
+        let rank = u128::MAX;
+        assert_eq!(permutation_rank(&nth_permutation(&items, rank).unwrap()), Some(rank));
+        let mut reversed = items.clone();
+        reversed.reverse();
+        assert_eq!(permutation_rank(&reversed), None);
+    }
+
+    #[test]
+    fn ranks_combinations() {
+        let mut rank = 0;
+        for a in 0..6 {
+            for b in a + 1..6 {
+                for c in b + 1..6 {
+                    assert_eq!(nth_combination(6, 3, rank), Some(vec!(a, b, c)));
+                    assert_eq!(combination_rank(&[a, b, c], 6), Some(rank));
+                    rank += 1;
+                }
+            }
+        }
+        assert_eq!(nth_combination(6, 3, 20), None);
+        assert_eq!(nth_combination(100, 50, binomial(100, 50).unwrap() - 1), Some((50..100).collect()));
+    }
+
+    #[test]
+    fn counts_selections() {
+        assert_eq!(binomial(23, 10), Some(1_144_066));
+        assert_eq!(binomial(5, 7), Some(0));
+        assert_eq!(binomial(130, 65), Some(95_067_625_827_960_698_145_584_333_020_095_113_100));
+        assert_eq!(binomial(132, 66), None);
+        assert_eq!(binomial(1_000_000, 2), Some(499_999_500_000));
+        assert_eq!(multinomial(&[2, 3, 4]), Some(1260));
+        assert_eq!(multinomial(&[100, 100]), None);
+        assert_eq!(factorial(34).map(|f| f % 1000), Some(0));
+        assert_eq!(factorial(35), None);
+    }
+
+    #[test]
+    fn builds_binomial_tables() {
+        let triangle = pascal_triangle(200);
+        let table = BinomialTable::new(199, 1_000_000_007);
+        for (n, row) in triangle.iter().enumerate().take(131) {
+            for (k, &c) in row.iter().enumerate() {
+                assert_eq!(Some(c), binomial(n as u64, k as u64));
+                assert_eq!(table.binomial(n, k) as u128, c % 1_000_000_007);
+            }
+        }
+        assert_eq!(triangle[199][100], u128::MAX);
+        assert_eq!(BinomialTable::new(6, 7).binomial(6, 3), 20 % 7);
+    }
+
+    #[test]
+    fn computes_stirling_and_catalan_numbers() {
+        assert_eq!(stirling_first(5, 2), Some(50));
+        assert_eq!(stirling_first(4, 4), Some(1));
+        assert_eq!(stirling_second(5, 2), Some(15));
+        assert_eq!(stirling_second(10, 4), Some(34_105));
+        assert_eq!(stirling_second(3, 0), Some(0));
+        assert_eq!(stirling_second(0, 0), Some(1));
+        assert_eq!(stirling_second(3, 5), Some(0));
+        assert_eq!(stirling_first(100, 1), None);
+        assert_eq!(stirling_second(100, 99), Some(4950));
+        let catalans = (0..10).map(|n| catalan(n).unwrap()).collect::<Vec<_>>();
+        assert_eq!(catalans, vec!(1, 1, 2, 5, 14, 42, 132, 429, 1430, 4862));
+        assert_eq!(catalan(66), Some(5_632_681_584_560_312_734_993_915_705_849_145_100));
+        assert_eq!(catalan(70), None);
+    }
+}
@@ -1,17 +1,133 @@
-pub fn dynamic_programming(values: &[usize], target: usize) -> usize {
-    let mut matrix = vec!();
-    matrix.resize_with(target + 1, || vec![0; values.len()]);
-    let mut index = 0;
-    while index <= target {
-        matrix[index][0] = 1;
-        for j in 1..matrix[index].len() {
-            if values[j] > index {
-                matrix[index][j] = matrix[index][j-1];
-            } else {
-                matrix[index][j] = matrix[index][j-1] + matrix[index - values[j]][j];
-            }
+use std::ops::{AddAssign, Sub};
+
+/// Counts in which sums of ways are accumulated: plain integers, `BigUint` when they grow too
+/// large, or `ModInt` to count modulo a constant.
+pub trait Count: Clone + From<u64> + for<'a> AddAssign<&'a Self> + Sub<Output=Self> {}
+
+impl<T: Clone + From<u64> + for<'a> AddAssign<&'a T> + Sub<Output=T>> Count for T {}
+
+/// Ways to make `target` from any number of each of the coin `values`, ignoring order.
+pub fn ways<T: Count>(values: &[usize], target: usize) -> T {
+    ways_up_to(values, target).pop().unwrap()
+}
+
+/// Ways to make every amount from 0 up to `target`, a single row updated one coin at a time.
+pub fn ways_up_to<T: Count>(values: &[usize], target: usize) -> Vec<T> {
+    let mut ways = vec!(T::from(0); target + 1);
+    ways[0] = T::from(1);
+    for &value in values.iter().filter(|&&v| v > 0) {
+        for amount in value..=target {
+            let (made, rest) = ways.split_at_mut(amount);
+            rest[0] += &made[amount - value];
+        }
+    }
+    ways
+}
+
+/// Ways to make `target` using each `(value, count)` coin at most `count` times.
+pub fn bounded_ways<T: Count>(coins: &[(usize, usize)], target: usize) -> T {
+    let mut ways = vec!(T::from(0); target + 1);
+    ways[0] = T::from(1);
+    for &(value, count) in coins.iter().filter(|&&(v, _)| v > 0) {
+        // with this coin, amount adds up the old ways of amount - j * value for j up to count,
+        // a window sliding along each residue class
+        let mut with_coin = ways.clone();
+        for amount in value..=target {
+            let mut window = with_coin[amount - value].clone();
+            window += &ways[amount];
+            with_coin[amount] = match count.checked_add(1).and_then(|c| c.checked_mul(value)) {
+                Some(width) if width <= amount => window - ways[amount - width].clone(),
+                _ => window,
+            };
+        }
+        ways = with_coin;
+    }
+    ways.pop().unwrap()
+}
+
+/// Fewest coins adding up to `target`, none when it cannot be made.
+pub fn min_coins(values: &[usize], target: usize) -> Option<usize> {
+    let mut fewest = vec!(None; target + 1);
+    fewest[0] = Some(0);
+    for amount in 1..=target {
+        fewest[amount] = values.iter()
+            .filter(|&&v| v > 0 && v <= amount)
+            .filter_map(|&v| fewest[amount - v])
+            .min()
+            .map(|coins: usize| coins + 1);
+    }
+    fewest[target]
+}
+
+/// Partition numbers p(0) to p(max), from Euler's pentagonal number theorem
+/// p(n) = Σ (-1)^(k+1) (p(n - k(3k-1)/2) + p(n - k(3k+1)/2)). O(max^1.5) instead of the
+/// O(max²) of counting ways with every value up to max.
+pub fn partitions<T: Count>(max: usize) -> Vec<T> {
+    let mut p: Vec<T> = Vec::with_capacity(max + 1);
+    p.push(T::from(1));
+    for n in 1..=max {
+        // signed terms summed apart so unsigned counts never go negative
+        let (mut added, mut removed) = (T::from(0), T::from(0));
+        for k in 1.. {
+            let pentagonal = k * (3 * k - 1) / 2;
+            if pentagonal > n { break; }
+            let sum = if k % 2 == 1 { &mut added } else { &mut removed };
+            *sum += &p[n - pentagonal];
+            if pentagonal + k <= n { *sum += &p[n - pentagonal - k]; }
         }
-        index += 1;
+        p.push(added - removed);
+    }
+    p
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use integers::biguint::BigUint;
+    use integers::modular::ModInt;
+
+    #[test]
+    fn counts_ways() {
+        let coins = [1, 2, 5, 10, 20, 50, 100, 200];
+        assert_eq!(ways::<u64>(&coins, 200), 73682);
+        assert_eq!(ways_up_to::<u64>(&coins, 10), vec!(1, 1, 2, 2, 3, 4, 5, 6, 7, 8, 11));
+        assert_eq!(ways::<u64>(&[2, 5], 3), 0);
+        assert_eq!(ways::<u64>(&[3, 7], 0), 1);
+        assert_eq!(ways::<ModInt<1000>>(&coins, 200).value(), 682);
+        let cents = ways::<BigUint>(&[1, 5, 10, 25, 50, 100], 100_000);
+        assert_eq!(cents.to_string(), "13398445413854501");
+    }
+
+    #[test]
+    fn counts_bounded_ways() {
+        assert_eq!(bounded_ways::<u64>(&[(1, 2), (2, 1), (5, 1)], 4), 1);
+        assert_eq!(bounded_ways::<u64>(&[(1, 1), (2, 1), (3, 1)], 3), 2);
+        assert_eq!(bounded_ways::<u64>(&[(1, 1)], 2), 0);
+        let coins = [1, 2, 5, 10, 20, 50, 100, 200];
+        let unbounded = coins.iter().map(|&v| (v, usize::MAX)).collect::<Vec<_>>();
+        assert_eq!(bounded_ways::<u64>(&unbounded, 200), 73682);
+        let ones = vec!((1, 1); 10);
+        assert_eq!(bounded_ways::<u64>(&ones, 4), 210);
+    }
+
+    #[test]
+    fn finds_fewest_coins() {
+        assert_eq!(min_coins(&[1, 3, 4], 6), Some(2));
+        assert_eq!(min_coins(&[1, 2, 5, 10, 20, 50, 100, 200], 289), Some(7));
+        assert_eq!(min_coins(&[4, 6], 7), None);
+        assert_eq!(min_coins(&[4, 6], 0), Some(0));
+    }
+
+    #[test]
+    fn counts_partitions() {
+        let p = partitions::<u64>(100);
+        assert_eq!(p[..8], [1, 1, 2, 3, 5, 7, 11, 15]);
+        assert_eq!(p[100], 190_569_292);
+        let values = (1..=60).collect::<Vec<_>>();
+        assert_eq!(partitions::<u64>(60)[60], ways::<u64>(&values, 60));
+        let big = partitions::<BigUint>(1000);
+        assert_eq!(big[1000].to_string(), "24061467864032622473692149727991");
+        let modular = partitions::<ModInt<1_000_000>>(1000);
+        assert_eq!(modular[1000].value(), 727991);
     }
-    *matrix.last().unwrap().last().unwrap()
 }
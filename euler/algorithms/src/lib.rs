@@ -1 +1,2 @@
+pub mod combinatorics;
 pub mod dynamic_programming;
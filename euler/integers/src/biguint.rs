@@ -0,0 +1,418 @@
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display, Formatter};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::digits::Digits;
+
+// below this many limbs schoolbook multiplication beats splitting
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// Unsigned integer of any size, stored as base 2^32 limbs with the least significant first and
+/// no leading zero limbs, zero being no limbs at all.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseBigUintError {
+    Empty,
+    InvalidRadix(u32),
+    InvalidDigit(char),
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn one() -> Self {
+        Self::from(1u32)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    fn from_limbs(mut limbs: Vec<u32>) -> Self {
+        trim(&mut limbs);
+        Self { limbs }
+    }
+
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            Some(last) => 32 * self.limbs.len() as u64 - last.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        self.to_u128().and_then(|n| u64::try_from(n).ok())
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.limbs.len() > 4 { return None }
+        Some(self.limbs.iter().rev().fold(0, |n, &limb| n << 32 | limb as u128))
+    }
+
+    pub fn pow(&self, exponent: u32) -> Self {
+        let mut result = Self::one();
+        let mut base = self.clone();
+        let mut exponent = exponent;
+        while exponent > 0 {
+            if exponent & 1 == 1 { result = &result * &base; }
+            exponent >>= 1;
+            if exponent > 0 { base = &base * &base; }
+        }
+        result
+    }
+
+    /// Quotient and remainder of a division by a single limb.
+    pub fn div_rem_small(&self, divisor: u32) -> (Self, u32) {
+        let mut quotient = vec!(0; self.limbs.len());
+        let mut remainder = 0u64;
+        for (i, &limb) in self.limbs.iter().enumerate().rev() {
+            let current = remainder << 32 | limb as u64;
+            quotient[i] = (current / divisor as u64) as u32;
+            remainder = current % divisor as u64;
+        }
+        (Self::from_limbs(quotient), remainder as u32)
+    }
+
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseBigUintError> {
+        if !(2..=36).contains(&radix) { return Err(ParseBigUintError::InvalidRadix(radix)) }
+        if s.is_empty() { return Err(ParseBigUintError::Empty) }
+        // as many digits as fit in a limb are folded in at once
+        let chunk = (u32::MAX.ilog(radix)) as usize;
+        let digits = s.chars()
+            .map(|c| c.to_digit(radix).ok_or(ParseBigUintError::InvalidDigit(c)))
+            .collect::<Result<Vec<u32>, _>>()?;
+        let mut n = Self::zero();
+        for group in digits.chunks(chunk) {
+            let value = group.iter().fold(0, |v, &d| v * radix + d);
+            n.mul_add_small(radix.pow(group.len() as u32), value);
+        }
+        Ok(n)
+    }
+
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix must be between 2 and 36");
+        if self.is_zero() { return "0".to_string() }
+        self.as_digits(radix).iter()
+            .map(|&d| char::from_digit(d, radix).unwrap())
+            .collect()
+    }
+
+    /// Digits in `radix`, the most significant first, to use the helpers of [`Digits`].
+    pub fn as_digits(&self, radix: u32) -> Digits<u32> {
+        let chunk = u32::MAX.ilog(radix);
+        let divisor = radix.pow(chunk);
+        let mut digits = VecDeque::new();
+        let mut n = self.clone();
+        while !n.is_zero() {
+            let (quotient, mut remainder) = n.div_rem_small(divisor);
+            for _ in 0..chunk {
+                digits.push_front(remainder % radix);
+                remainder /= radix;
+            }
+            n = quotient;
+        }
+        while digits.front() == Some(&0) {
+            digits.pop_front();
+        }
+        Digits::from_digits(radix, digits)
+    }
+
+    pub fn as_decimal(&self) -> Digits<u32> {
+        self.as_digits(10)
+    }
+
+    pub fn as_binary(&self) -> Digits<u32> {
+        self.as_digits(2)
+    }
+
+    pub fn digit_sum(&self) -> u64 {
+        self.as_decimal().iter().map(|&d| d as u64).sum()
+    }
+
+    fn mul_add_small(&mut self, factor: u32, addend: u32) {
+        let mut carry = addend as u64;
+        for limb in &mut self.limbs {
+            let current = *limb as u64 * factor as u64 + carry;
+            *limb = current as u32;
+            carry = current >> 32;
+        }
+        if carry > 0 { self.limbs.push(carry as u32); }
+        trim(&mut self.limbs);
+    }
+}
+
+impl From<u32> for BigUint {
+    fn from(n: u32) -> Self {
+        Self::from(n as u128)
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> Self {
+        Self::from(n as u128)
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(n: u128) -> Self {
+        Self::from_limbs((0..4).map(|i| (n >> (32 * i)) as u32).collect())
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_radix(s, 10)
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_str_radix(10))
+    }
+}
+
+impl Debug for BigUint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs.len().cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, rhs: &BigUint) -> BigUint {
+        let mut limbs = self.limbs.clone();
+        add_shifted(&mut limbs, &rhs.limbs, 0);
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Sub<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    /// Panics when `rhs` is larger.
+    fn sub(self, rhs: &BigUint) -> BigUint {
+        assert!(*self >= *rhs, "subtraction underflow");
+        let mut limbs = self.limbs.clone();
+        sub_in_place(&mut limbs, &rhs.limbs);
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, rhs: &BigUint) -> BigUint {
+        BigUint::from_limbs(mul_limbs(&self.limbs, &rhs.limbs))
+    }
+}
+
+macro_rules! forward_binary_op {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident) => {
+        impl $Op for BigUint {
+            type Output = BigUint;
+
+            fn $op(self, rhs: BigUint) -> BigUint {
+                (&self).$op(&rhs)
+            }
+        }
+
+        impl $Op<&BigUint> for BigUint {
+            type Output = BigUint;
+
+            fn $op(self, rhs: &BigUint) -> BigUint {
+                (&self).$op(rhs)
+            }
+        }
+
+        impl $OpAssign<&BigUint> for BigUint {
+            fn $op_assign(&mut self, rhs: &BigUint) {
+                *self = (&*self).$op(rhs);
+            }
+        }
+
+        impl $OpAssign for BigUint {
+            fn $op_assign(&mut self, rhs: BigUint) {
+                *self = (&*self).$op(&rhs);
+            }
+        }
+    }
+}
+
+forward_binary_op!(Add, add, AddAssign, add_assign);
+forward_binary_op!(Sub, sub, SubAssign, sub_assign);
+forward_binary_op!(Mul, mul, MulAssign, mul_assign);
+
+impl Sum for BigUint {
+    fn sum<I: Iterator<Item=Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |sum, n| sum + n)
+    }
+}
+
+impl Product for BigUint {
+    fn product<I: Iterator<Item=Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |product, n| product * n)
+    }
+}
+
+fn trim(limbs: &mut Vec<u32>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+/// Adds `b` shifted left by `shift` limbs to `a`.
+fn add_shifted(a: &mut Vec<u32>, b: &[u32], shift: usize) {
+    if a.len() < b.len() + shift { a.resize(b.len() + shift, 0); }
+    let mut carry = 0u64;
+    for (i, limb) in a.iter_mut().enumerate().skip(shift) {
+        let sum = *limb as u64 + b.get(i - shift).copied().unwrap_or(0) as u64 + carry;
+        *limb = sum as u32;
+        carry = sum >> 32;
+        if carry == 0 && i >= b.len() + shift { break; }
+    }
+    if carry > 0 { a.push(carry as u32); }
+}
+
+/// Subtracts `b` from `a`, which must not be smaller.
+fn sub_in_place(a: &mut [u32], b: &[u32]) {
+    let mut borrow = 0i64;
+    for (i, limb) in a.iter_mut().enumerate() {
+        let difference = *limb as i64 - b.get(i).copied().unwrap_or(0) as i64 - borrow;
+        *limb = difference.rem_euclid(1 << 32) as u32;
+        borrow = (difference < 0) as i64;
+        if borrow == 0 && i >= b.len() { break; }
+    }
+}
+
+fn mul_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() { return vec!() }
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD { return schoolbook(a, b) }
+    karatsuba(a, b)
+}
+
+fn schoolbook(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product = vec!(0u32; a.len() + b.len());
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let current = product[i + j] as u64 + x as u64 * y as u64 + carry;
+            product[i + j] = current as u32;
+            carry = current >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    product
+}
+
+/// Splits both at m limbs, a = a1 * B^m + a0, and gets the middle term from a single product:
+/// a * b = z2 * B^2m + ((a0 + a1)(b0 + b1) - z2 - z0) * B^m + z0.
+fn karatsuba(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let m = a.len().max(b.len()) / 2;
+    // lopsided operands are cut into pieces the size of the short one
+    if a.len().min(b.len()) <= m {
+        let (long, short) = if a.len() > b.len() { (a, b) } else { (b, a) };
+        let mut product = vec!();
+        for (k, piece) in long.chunks(short.len()).enumerate() {
+            let mut partial = mul_limbs(piece, short);
+            trim(&mut partial);
+            add_shifted(&mut product, &partial, k * short.len());
+        }
+        return product;
+    }
+    let (a0, a1) = a.split_at(m);
+    let (b0, b1) = b.split_at(m);
+    let mut z0 = mul_limbs(a0, b0);
+    let mut z2 = mul_limbs(a1, b1);
+    trim(&mut z0);
+    trim(&mut z2);
+    let mut a_sum = a0.to_vec();
+    add_shifted(&mut a_sum, a1, 0);
+    let mut b_sum = b0.to_vec();
+    add_shifted(&mut b_sum, b1, 0);
+    let mut z1 = mul_limbs(&a_sum, &b_sum);
+    sub_in_place(&mut z1, &z0);
+    sub_in_place(&mut z1, &z2);
+    trim(&mut z1);
+    let mut product = z0;
+    add_shifted(&mut product, &z1, m);
+    add_shifted(&mut product, &z2, 2 * m);
+    product
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn random(seed: &mut u64, limbs: usize) -> BigUint {
+        BigUint::from_limbs((0..limbs).map(|_| {
+            *seed ^= *seed << 13;
+            *seed ^= *seed >> 7;
+            *seed ^= *seed << 17;
+            *seed as u32
+        }).collect())
+    }
+
+    #[test]
+    fn computes_large_numbers() {
+        assert_eq!(BigUint::from(2u32).pow(1000).digit_sum(), 1366);
+        assert_eq!((1..=100u32).map(BigUint::from).product::<BigUint>().digit_sum(), 648);
+        assert_eq!(BigUint::from(u128::MAX) + BigUint::one(), BigUint::from(2u32).pow(128));
+        assert_eq!(BigUint::from(2u32).pow(128) - BigUint::one(), BigUint::from(u128::MAX));
+        assert_eq!((BigUint::from(10u32).pow(30) - BigUint::from(10u32).pow(30)).to_string(), "0");
+        assert_eq!(BigUint::from(7u32).pow(100).div_rem_small(7), (BigUint::from(7u32).pow(99), 0));
+        assert!(BigUint::from(10u32).pow(20) > BigUint::from(u64::MAX));
+        assert_eq!(BigUint::from(u64::MAX).to_u64(), Some(u64::MAX));
+        assert_eq!(BigUint::from(2u32).pow(64).to_u64(), None);
+    }
+
+    #[test]
+    fn karatsuba_agrees_with_schoolbook() {
+        let mut seed = 88_172_645_463_325_252;
+        for (x, y) in [(32, 32), (100, 37), (257, 256), (500, 31), (1000, 600)] {
+            let a = random(&mut seed, x);
+            let b = random(&mut seed, y);
+            let expected = BigUint::from_limbs(schoolbook(&a.limbs, &b.limbs));
+            assert_eq!(&a * &b, expected, "{x} x {y}");
+            assert_eq!(&b * &a, expected, "{y} x {x}");
+        }
+    }
+
+    #[test]
+    fn parses_and_formats_in_any_base() {
+        let n: BigUint = "123456789012345678901234567890".parse().unwrap();
+        assert_eq!(n.to_string(), "123456789012345678901234567890");
+        assert_eq!(n.to_str_radix(16), "18ee90ff6c373e0ee4e3f0ad2");
+        assert_eq!(BigUint::from_str_radix("18ee90ff6c373e0ee4e3f0ad2", 16), Ok(n.clone()));
+        assert_eq!(BigUint::from_str_radix(&n.to_str_radix(36), 36), Ok(n.clone()));
+        assert_eq!(BigUint::from_str_radix(&n.to_str_radix(2), 2), Ok(n));
+        assert_eq!(BigUint::from(5u32).as_binary().number(), 5);
+        assert_eq!("0012".parse(), Ok(BigUint::from(12u32)));
+        assert_eq!("12a".parse::<BigUint>(), Err(ParseBigUintError::InvalidDigit('a')));
+        assert_eq!("".parse::<BigUint>(), Err(ParseBigUintError::Empty));
+    }
+}
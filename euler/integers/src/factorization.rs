@@ -0,0 +1,170 @@
+use num_integer::Integer as _;
+
+use crate::Integer;
+use crate::factorized::Factorized;
+use crate::modular::mul_mod;
+use crate::primality::is_prime_u128;
+
+// cofactors left after dividing these out are handed to Pollard's rho
+const TRIAL_DIVISION_LIMIT: u128 = 1000;
+
+/// Smallest prime factor of every number up to `max`, from a linear sieve. Any of them is then
+/// factored in as many steps as it has prime factors.
+pub struct SmallestPrimeFactors {
+    factors: Vec<u32>,
+    primes: Vec<u32>,
+}
+
+impl SmallestPrimeFactors {
+    pub fn new(max: usize) -> Self {
+        let mut factors = vec!(0u32; max + 1);
+        let mut primes = vec!();
+        for n in 2..=max {
+            if factors[n] == 0 {
+                factors[n] = n as u32;
+                primes.push(n as u32);
+            }
+            // every composite is crossed off once, by its smallest prime factor
+            for &p in &primes {
+                if p > factors[n] || n * p as usize > max { break; }
+                factors[n * p as usize] = p;
+            }
+        }
+        Self { factors, primes }
+    }
+
+    pub fn max(&self) -> usize {
+        self.factors.len() - 1
+    }
+
+    pub fn smallest(&self, n: usize) -> usize {
+        self.factors[n] as usize
+    }
+
+    pub fn primes(&self) -> &[u32] {
+        &self.primes
+    }
+
+    /// Prime factors of `n` in increasing order, repeated by multiplicity.
+    pub fn prime_factors(&self, n: usize) -> impl Iterator<Item=usize> + '_ {
+        let mut n = n;
+        std::iter::from_fn(move || {
+            if n <= 1 { return None }
+            let p = self.smallest(n);
+            n /= p;
+            Some(p)
+        })
+    }
+
+    pub fn factorize(&self, n: usize) -> Factorized<usize> {
+        Factorized::from_primes(n, self.prime_factors(n))
+    }
+}
+
+/// Prime factors of `n` in increasing order, repeated by multiplicity. Small factors are found
+/// by trial division, the rest by Pollard's rho.
+pub fn prime_factors(n: u128) -> Vec<u128> {
+    let mut n = n;
+    let mut factors = vec!();
+    let mut p = 2;
+    while p < TRIAL_DIVISION_LIMIT && p * p <= n {
+        while n.is_multiple_of(p) {
+            factors.push(p);
+            n /= p;
+        }
+        p += if p == 2 { 1 } else { 2 };
+    }
+    let mut composites = vec!();
+    if n > 1 { composites.push(n); }
+    while let Some(n) = composites.pop() {
+        if is_prime_u128(n) {
+            factors.push(n);
+        } else {
+            let d = pollard_rho(n);
+            composites.push(d);
+            composites.push(n / d);
+        }
+    }
+    factors.sort();
+    factors
+}
+
+/// A non-trivial factor of the composite `n`, not necessarily prime.
+pub fn pollard_rho(n: u128) -> u128 {
+    if n.is_multiple_of(2) { return 2 }
+    (1..)
+        .find_map(|c| match u64::try_from(n) {
+            Ok(_) => brent(n, c, |a, b| a * b % n),
+            Err(_) => brent(n, c, |a, b| mul_mod(a, b, n)),
+        })
+        .unwrap()
+}
+
+/// Pollard's rho on x² + c with Brent's cycle detection, multiplying the differences together
+/// to take one gcd per batch. Fails when the cycle closes on `n` itself.
+fn brent(n: u128, c: u128, mul: impl Fn(u128, u128) -> u128) -> Option<u128> {
+    const BATCH: u128 = 128;
+    let f = |x: u128| {
+        let square = mul(x, x);
+        if square >= n - c { square - (n - c) } else { square + c }
+    };
+    let (mut y, mut r, mut q, mut g) = (2, 1, 1, 1);
+    let (mut x, mut saved) = (y, y);
+    while g == 1 {
+        x = y;
+        for _ in 0..r { y = f(y); }
+        let mut k = 0;
+        while k < r && g == 1 {
+            saved = y;
+            for _ in 0..BATCH.min(r - k) {
+                y = f(y);
+                q = mul(q, x.abs_diff(y));
+            }
+            g = q.gcd(&n);
+            k += BATCH;
+        }
+        r *= 2;
+    }
+    if g == n {
+        // the batch overshot, walk it again one step at a time
+        loop {
+            saved = f(saved);
+            g = x.abs_diff(saved).gcd(&n);
+            if g > 1 { break; }
+        }
+    }
+    if g == n { None } else { Some(g) }
+}
+
+pub(crate) fn factorize<T: Integer>(n: T) -> Factorized<T> {
+    let primes = n.to_u128().filter(|&n| n > 1).map(prime_factors).unwrap_or_default();
+    Factorized::from_primes(n, primes.into_iter().map(|p| T::from_u128(p).unwrap()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sieves_smallest_prime_factors() {
+        let factors = SmallestPrimeFactors::new(100);
+        assert_eq!(factors.smallest(91), 7);
+        assert_eq!(factors.smallest(97), 97);
+        assert_eq!(factors.prime_factors(72).collect::<Vec<_>>(), vec!(2, 2, 2, 3, 3));
+        assert_eq!(factors.primes().len(), 25);
+        assert_eq!(factors.factorize(60), 60.factorize());
+    }
+
+    #[test]
+    fn factors_large_numbers() {
+        assert_eq!(prime_factors(600_851_475_143), vec!(71, 839, 1471, 6857));
+        assert_eq!(prime_factors(1_000_000_007 * 998_244_353), vec!(998_244_353, 1_000_000_007));
+        assert_eq!(prime_factors(u64::MAX as u128), vec!(3, 5, 17, 257, 641, 65_537, 6_700_417));
+        let p = 1_000_000_007;
+        let q = (1 << 89) - 1;
+        assert_eq!(prime_factors(p * q), vec!(p, q));
+        assert!((3..1000).step_by(2).all(|n| pollard_rho(n * 1009) > 1));
+        assert_eq!(prime_factors((1 << 89) - 1), vec!((1 << 89) - 1));
+        assert_eq!(prime_factors(1), vec!());
+    }
+}
@@ -11,15 +11,13 @@ pub struct Factorized<T: Integer> {
 
 impl<T: Integer> Factorized<T> {
     pub(crate) fn new(base: T) -> Self {
-        let mut n = base;
+        crate::factorization::factorize(base)
+    }
+
+    pub(crate) fn from_primes(base: T, primes: impl Iterator<Item=T>) -> Self {
         let mut factors = HashMap::new();
-        while n > T::one() {
-            let mut f = T::one() + T::one();
-            while !(n % f).is_zero() {
-                f += T::one();
-            }
-            *factors.entry(f).or_insert(T::zero()) += T::one();
-            n /= f;
+        for p in primes {
+            *factors.entry(p).or_insert(T::zero()) += T::one();
         }
         Self { base, exponent: T::one(), factors }
     }
@@ -36,9 +34,34 @@ impl<T: Integer> Factorized<T> {
         self.factors.keys().max().cloned()
     }
     
+    /// Prime factors with their exponents, in no particular order.
+    pub fn factors(&self) -> impl Iterator<Item=(T, T)> + '_ {
+        self.factors.iter().map(|(&f, &c)| (f, c))
+    }
+
     pub fn distinct_count(&self) -> usize {
         self.factors.keys().count()
     }
+
+    pub fn divisor_count(&self) -> usize {
+        self.factors.values().map(|c| c.to_usize().unwrap() + 1).product()
+    }
+
+    /// Every divisor, in increasing order, multiplied out of the prime factors.
+    pub fn divisors(&self) -> Vec<T> {
+        let mut divisors = vec!(T::one());
+        for (&f, &c) in &self.factors {
+            let mut powers = vec!();
+            let mut power = T::one();
+            for _ in 0..c.to_usize().unwrap() {
+                power *= f;
+                powers.extend(divisors.iter().map(|&d| d * power));
+            }
+            divisors.extend(powers);
+        }
+        divisors.sort();
+        divisors
+    }
 }
 
 impl<T: Integer> PartialEq for Factorized<T> {
@@ -56,3 +79,17 @@ impl<T: Integer> Debug for Factorized<T> {
                                      .join(" * ")))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn enumerates_divisors() {
+        assert_eq!(12u32.factorize().divisors(), vec!(1, 2, 3, 4, 6, 12));
+        assert_eq!(1u32.factorize().divisors(), vec!(1));
+        assert_eq!(28u64.factorize().pow(2).divisor_count(), 15);
+        assert_eq!(735_134_400u64.factorize().divisor_count(), 1344);
+        assert_eq!(735_134_400u64.factorize().divisors().len(), 1344);
+    }
+}
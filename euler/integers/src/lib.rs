@@ -1,4 +1,8 @@
+pub mod biguint;
 pub mod factorized;
+pub mod factorization;
+pub mod modular;
+pub mod primality;
 pub mod digits;
 pub mod truncating;
 
@@ -15,6 +19,8 @@ num_integer::Integer +
 num_traits::NumAssignOps +
 num_traits::NumOps +
 num_integer::Roots +
+num_traits::ToPrimitive +
+num_traits::FromPrimitive +
 {
     fn two() -> Self;
     fn ten() -> Self;
@@ -28,25 +34,17 @@ num_integer::Roots +
         Digits::from_number(Self::from(10), *self)
     }
 
+    fn digit_sum(&self) -> Self {
+        self.as_decimal().iter().copied().sum()
+    }
+
     fn factorize(&self) -> Factorized<Self> {
         Factorized::new(*self)
     }
 
     fn proper_divisors(&self) -> Vec<Self> {
-        let n = *self;
-        let mut divisors = vec!(Self::one());
-        let max = n.sqrt();
-        let mut d = Self::two();
-        while d <= max {
-            if n % d == Self::zero() {
-                divisors.push(d);
-                if d * d != n {
-                    divisors.push(n / d);
-                }
-            }
-            d += Self::one();
-        }
-        divisors.sort();
+        let mut divisors = self.factorize().divisors();
+        divisors.pop();
         divisors
     }
 }
@@ -82,4 +80,10 @@ mod test {
     fn finds_proper_divisors_of_a_number() {
         assert_eq!(12u32.proper_divisors(), vec!(1, 2, 3, 4, 6));
     }
+
+    #[test]
+    fn sums_decimal_digits() {
+        assert_eq!(9875u32.digit_sum(), 29);
+        assert_eq!(0u8.digit_sum(), 0);
+    }
 }
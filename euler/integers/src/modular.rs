@@ -0,0 +1,338 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// `a * b % m` without overflowing, by doubling when the product does not fit.
+pub fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) { return product % m }
+    let mut result: u128 = 0;
+    let mut a = a % m;
+    let mut b = b;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = if result >= m - a { result - (m - a) } else { result + a };
+        }
+        a = if a >= m - a { a - (m - a) } else { a + a };
+        b >>= 1;
+    }
+    result
+}
+
+pub fn pow_mod(base: u128, exponent: u128, m: u128) -> u128 {
+    let mut result = 1 % m;
+    let mut base = base % m;
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 { result = mul_mod(result, base, m); }
+        base = mul_mod(base, base, m);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Greatest common divisor `g` of `a` and `b` with Bézout coefficients, `a * x + b * y = g`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 { (-r0, -x0, -y0) } else { (r0, x0, y0) }
+}
+
+/// Inverse of `a` modulo `m`, when they are coprime. Coefficients are kept modulo `m` so that
+/// any u128 modulus works.
+pub fn inverse_mod(a: u128, m: u128) -> Option<u128> {
+    let (mut r0, mut r1) = (m, a % m);
+    let (mut t0, mut t1) = (0, 1 % m);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        let qt = mul_mod(q, t1, m);
+        (t0, t1) = (t1, if t0 >= qt { t0 - qt } else { m - (qt - t0) });
+    }
+    if r0 == 1 { Some(t0) } else { None }
+}
+
+/// Chinese Remainder Theorem, the x with x ≡ r (mod m) for every `(r, m)`, and the modulus it
+/// is unique for. Moduli need not be coprime, `None` when the congruences contradict each other
+/// or the combined modulus overflows.
+pub fn crt(congruences: &[(u128, u128)]) -> Option<(u128, u128)> {
+    congruences.iter().try_fold((0, 1), |(x, m), &(r, n)| {
+        let g = gcd(m, n);
+        let r = r % n;
+        let difference = if r >= x % n { r - x % n } else { n - (x % n - r) };
+        if !difference.is_multiple_of(g) { return None }
+        let (m_g, n_g) = (m / g, n / g);
+        let k = mul_mod(difference / g, inverse_mod(m_g, n_g)?, n_g);
+        let lcm = m_g.checked_mul(n)?;
+        let step = mul_mod(m, k, lcm);
+        Some((if x >= lcm - step { x - (lcm - step) } else { x + step }, lcm))
+    })
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Baby-step giant-step, the smallest x with base^x ≡ target (mod m), for `base` coprime with
+/// `m`. Takes O(√m) time and memory.
+pub fn discrete_log(base: u64, target: u64, m: u64) -> Option<u64> {
+    let (base, target, m) = (base as u128, target as u128 % m as u128, m as u128);
+    let steps = (m as u64).isqrt() as u128 + 1;
+    let mut baby = HashMap::new();
+    let mut power = 1 % m;
+    for j in 0..steps {
+        baby.entry(power).or_insert(j);
+        power = power * base % m;
+    }
+    // power is now base^steps, walk target * base^(-steps * i)
+    let giant = inverse_mod(power, m)?;
+    let mut gamma = target;
+    for i in 0..steps {
+        if let Some(&j) = baby.get(&gamma) { return Some((i * steps + j) as u64) }
+        gamma = gamma * giant % m;
+    }
+    None
+}
+
+/// Integer modulo the constant `M`, which must be at least 1.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ModInt<const M: u64>(u64);
+
+impl<const M: u64> ModInt<M> {
+    pub fn new(value: u64) -> Self {
+        Self(value % M)
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(&self, exponent: u64) -> Self {
+        Self(pow_mod(self.0 as u128, exponent as u128, M as u128) as u64)
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        inverse_mod(self.0 as u128, M as u128).map(|i| Self(i as u64))
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const M: u64> Display for ModInt<M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(((self.0 as u128 + rhs.0 as u128) % M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        if self.0 == 0 { self } else { Self(M - self.0) }
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self((self.0 as u128 * rhs.0 as u128 % M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+
+    /// Multiplies by the inverse of `rhs`, panics when it has none.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inverse().expect("divisor is not invertible")
+    }
+}
+
+impl<const M: u64> AddAssign for ModInt<M> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const M: u64> SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const M: u64> AddAssign<&Self> for ModInt<M> {
+    fn add_assign(&mut self, rhs: &Self) {
+        *self = *self + *rhs;
+    }
+}
+
+impl<const M: u64> MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const M: u64> std::iter::Sum for ModInt<M> {
+    fn sum<I: Iterator<Item=Self>>(iter: I) -> Self {
+        iter.fold(Self(0), |sum, n| sum + n)
+    }
+}
+
+/// Montgomery arithmetic for an odd modulus only known at run time. Numbers are kept as
+/// a * 2^64 mod m so that products need no division.
+#[derive(Clone, Copy, Debug)]
+pub struct Montgomery {
+    modulus: u64,
+    // -m^(-1) mod 2^64
+    negated_inverse: u64,
+    // 2^128 mod m, to move numbers in
+    r2: u64,
+}
+
+impl Montgomery {
+    /// Panics when `modulus` is even.
+    pub fn new(modulus: u64) -> Self {
+        assert!(modulus % 2 == 1, "Montgomery modulus must be odd");
+        // Newton's iteration doubles the correct low bits each step, 3 are right from the start
+        let mut inverse = modulus;
+        for _ in 0..5 {
+            inverse = inverse.wrapping_mul(2u64.wrapping_sub(modulus.wrapping_mul(inverse)));
+        }
+        let r2 = ((u128::MAX % modulus as u128 + 1) % modulus as u128) as u64;
+        Self { modulus, negated_inverse: inverse.wrapping_neg(), r2 }
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// Montgomery form of `a`.
+    pub fn to_form(&self, a: u64) -> u64 {
+        self.reduce((a % self.modulus) as u128 * self.r2 as u128)
+    }
+
+    pub fn from_form(&self, a: u64) -> u64 {
+        self.reduce(a as u128)
+    }
+
+    /// Product of two numbers in Montgomery form.
+    pub fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    pub fn mul_mod(&self, a: u64, b: u64) -> u64 {
+        self.from_form(self.mul(self.to_form(a), self.to_form(b)))
+    }
+
+    pub fn pow_mod(&self, base: u64, exponent: u64) -> u64 {
+        let mut result = self.to_form(1);
+        let mut base = self.to_form(base);
+        let mut exponent = exponent;
+        while exponent > 0 {
+            if exponent & 1 == 1 { result = self.mul(result, base); }
+            base = self.mul(base, base);
+            exponent >>= 1;
+        }
+        self.from_form(result)
+    }
+
+    /// t * 2^(-64) mod m, for t < m * 2^64.
+    fn reduce(&self, t: u128) -> u64 {
+        let q = (t as u64).wrapping_mul(self.negated_inverse);
+        let (sum, carry) = t.overflowing_add(q as u128 * self.modulus as u128);
+        let reduced = (sum >> 64) | (carry as u128) << 64;
+        if reduced >= self.modulus as u128 { (reduced - self.modulus as u128) as u64 } else { reduced as u64 }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn multiplies_without_overflowing() {
+        let m = u128::MAX - 158;
+        assert_eq!(mul_mod(m - 1, m - 1, m), 1);
+        assert_eq!(mul_mod(1 << 100, 1 << 100, (1 << 127) - 1), 1 << 73);
+        assert_eq!(pow_mod(2, 1 << 100, 1_000_000_007), pow_mod(2, (1 << 100) % 1_000_000_006, 1_000_000_007));
+        assert_eq!(pow_mod(5, 0, 1), 0);
+    }
+
+    #[test]
+    fn inverts() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(inverse_mod(3, 11), Some(4));
+        assert_eq!(inverse_mod(6, 9), None);
+        let m = (1 << 127) - 1;
+        let a = 123_456_789_012_345_678_901_234_567;
+        assert_eq!(mul_mod(a, inverse_mod(a, m).unwrap(), m), 1);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn finds_discrete_logarithms() {
+        assert_eq!(discrete_log(2, 1, 11), Some(0));
+        assert_eq!(discrete_log(3, 13, 17), Some(4));
+        assert_eq!(discrete_log(2, 3, 7), None);
+        let m = 1_000_000_007;
+        assert_eq!(discrete_log(5, pow_mod(5, 123_456_789, m) as u64, m as u64), Some(123_456_789));
+    }
+
+    #[test]
+    fn computes_modulo_a_constant() {
+        type Mod7 = ModInt<7>;
+        let a = Mod7::new(5);
+        assert_eq!((a + Mod7::new(4)).value(), 2);
+        assert_eq!((a - Mod7::new(6)).value(), 6);
+        assert_eq!((a * a).value(), 4);
+        assert_eq!((Mod7::new(1) / a * a).value(), 1);
+        assert_eq!(a.pow(6), Mod7::new(1));
+        assert_eq!(ModInt::<6>::new(2).inverse(), None);
+    }
+
+    #[test]
+    fn multiplies_in_montgomery_form() {
+        for m in [3, 1_000_000_007, (1 << 61) - 1, u64::MAX - 58] {
+            let montgomery = Montgomery::new(m);
+            for (a, b) in [(0, 5), (2, m - 1), (m - 1, m - 1), (123_456_789_123, 987_654_321_987)] {
+                assert_eq!(montgomery.mul_mod(a, b) as u128, mul_mod(a as u128, b as u128, m as u128));
+            }
+            assert_eq!(montgomery.pow_mod(3, m - 1) as u128, pow_mod(3, m as u128 - 1, m as u128));
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::modular::mul_mod;
+
+const SMALL_PRIMES: [u64; 16] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+
+// Miller–Rabin with the first 13 primes as bases is exact below this bound
+const DETERMINISTIC_BOUND: u128 = 3_317_044_064_679_887_385_961_981;
+
+fn trial_division(n: u64) -> Option<bool> {
+    for p in SMALL_PRIMES {
+        if n == p { return Some(true) }
+        if n.is_multiple_of(p) { return Some(false) }
+    }
+    if n < 59 * 59 { Some(n > 1) } else { None }
+}
+
+/// Deterministic for every u64.
+pub fn is_prime_u64(n: u64) -> bool {
+    if let Some(prime) = trial_division(n) { return prime }
+    let n = n as u128;
+    miller_rabin(n, &SMALL_PRIMES[..12], |a, b| a * b % n)
+}
+
+/// Deterministic below 3.3 * 10^24. Above that it is the Baillie–PSW test, which has no known
+/// counterexample but is not proven exact.
+pub fn is_prime_u128(n: u128) -> bool {
+    if let Ok(small) = u64::try_from(n) { return is_prime_u64(small) }
+    if SMALL_PRIMES.iter().any(|&p| n.is_multiple_of(p as u128)) { return false }
+    let mul = |a, b| mul_mod(a, b, n);
+    if n < DETERMINISTIC_BOUND { return miller_rabin(n, &SMALL_PRIMES[..13], mul) }
+    miller_rabin(n, &[2], mul) && strong_lucas(n)
+}
+
+/// Strong probable prime test to every base, `n` odd and larger than the bases.
+fn miller_rabin(n: u128, bases: &[u64], mul: impl Fn(u128, u128) -> u128) -> bool {
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    bases.iter().all(|&a| {
+        let mut x = pow_mod(a as u128, d, &mul);
+        if x == 1 || x == n - 1 { return true }
+        for _ in 1..s {
+            x = mul(x, x);
+            if x == n - 1 { return true }
+        }
+        false
+    })
+}
+
+/// Strong Lucas probable prime test with Selfridge's parameters, `n` odd and not divisible by
+/// the small primes.
+fn strong_lucas(n: u128) -> bool {
+    if n.isqrt() * n.isqrt() == n { return false }
+    // first D of 5, -7, 9, -11, ... with (D / n) = -1, then P = 1 and Q = (1 - D) / 4
+    let mut d: i128 = 5;
+    loop {
+        match jacobi(residue(d, n), n) {
+            -1 => break,
+            0 if d.unsigned_abs() != n => return false,
+            _ => d = if d > 0 { -d - 2 } else { -d + 2 },
+        }
+    }
+    let (d, q) = (residue(d, n), residue((1 - d) / 4, n));
+    let add = |a: u128, b: u128| if a >= n - b { a - (n - b) } else { a + b };
+    let sub = |a: u128, b: u128| if a >= b { a - b } else { a + (n - b) };
+    let half = |a: u128| if a & 1 == 0 { a / 2 } else { a / 2 + n / 2 + 1 };
+    // n + 1 = k * 2^s with k odd, n + 1 cannot overflow as 3 divides u128::MAX
+    let s = (n + 1).trailing_zeros();
+    let k = (n + 1) >> s;
+    // U, V and Q^i for i the leading bits of k, starting from i = 1
+    let (mut u, mut v, mut qi) = (1, 1, q);
+    for bit in (0..127 - k.leading_zeros()).rev() {
+        u = mul_mod(u, v, n);
+        v = sub(mul_mod(v, v, n), add(qi, qi));
+        qi = mul_mod(qi, qi, n);
+        if k >> bit & 1 == 1 {
+            (u, v) = (half(add(u, v)), half(add(mul_mod(d, u, n), v)));
+            qi = mul_mod(qi, q, n);
+        }
+    }
+    if u == 0 || v == 0 { return true }
+    for _ in 1..s {
+        v = sub(mul_mod(v, v, n), add(qi, qi));
+        if v == 0 { return true }
+        qi = mul_mod(qi, qi, n);
+    }
+    false
+}
+
+fn residue(a: i128, n: u128) -> u128 {
+    let r = a.unsigned_abs() % n;
+    if a < 0 && r != 0 { n - r } else { r }
+}
+
+/// Jacobi symbol (a / n) for odd n.
+fn jacobi(mut a: u128, mut n: u128) -> i8 {
+    a %= n;
+    let mut result = 1;
+    while a != 0 {
+        let twos = a.trailing_zeros();
+        a >>= twos;
+        if twos & 1 == 1 && matches!(n % 8, 3 | 5) { result = -result; }
+        if a % 4 == 3 && n % 4 == 3 { result = -result; }
+        (a, n) = (n % a, a);
+    }
+    if n == 1 { result } else { 0 }
+}
+
+fn pow_mod(mut base: u128, mut exponent: u128, mul: impl Fn(u128, u128) -> u128) -> u128 {
+    let mut result = 1;
+    while exponent > 0 {
+        if exponent & 1 == 1 { result = mul(result, base); }
+        base = mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tests_large_numbers() {
+        assert!(is_prime_u64((1 << 61) - 1));
+        assert!(is_prime_u64(18_446_744_073_709_551_557));
+        assert!(!is_prime_u64(u64::MAX));
+        // strong pseudoprimes to the first few prime bases
+        assert!(!is_prime_u64(3_215_031_751));
+        assert!(!is_prime_u64(3_825_123_056_546_413_051));
+        assert!(!is_prime_u128(318_665_857_834_031_151_167_461));
+        assert!(is_prime_u128((1 << 89) - 1));
+        assert!(is_prime_u128((1 << 127) - 1));
+        assert!(!is_prime_u128((1 << 127) + 1));
+        assert!(!is_prime_u128(((1 << 61) - 1) * ((1 << 31) - 1)));
+        // above the Miller–Rabin bound, a prime squared and a product of two large primes
+        assert!(!is_prime_u128(18_446_744_073_709_551_557 * 18_446_744_073_709_551_557));
+        assert!(!is_prime_u128(((1 << 89) - 1) * 1_000_000_007));
+        assert!(is_prime_u128(u128::MAX - 158));
+    }
+
+    #[test]
+    fn finds_the_strong_lucas_pseudoprimes() {
+        let pseudoprimes: Vec<u128> = (59..100_000u128)
+            .step_by(2)
+            .filter(|&n| !is_prime_u64(n as u64) && SMALL_PRIMES.iter().all(|&p| !n.is_multiple_of(p as u128)))
+            .filter(|&n| strong_lucas(n))
+            .collect();
+        // 5459 and 5777 are pseudoprimes too, but multiples of 53
+        assert_eq!(pseudoprimes, vec!(10877, 16109, 18971, 22499, 24569, 25199, 40309, 58519, 75077, 97439));
+        assert!((59..100_000u64).filter(|&n| is_prime_u64(n)).all(|n| strong_lucas(n as u128)));
+    }
+}
@@ -1,6 +1,6 @@
 use integers::Integer;
 
-use crate::polygonal::{Polygonal, PolygonalIterator};
+use crate::polygonal::{Figurate, PolygonalIterator};
 
 pub trait Hexagonal: Integer {
     fn hexagonal_root(self) -> Option<Self>;
@@ -1,11 +1,17 @@
 mod triangular;
 mod primes;
+mod primality;
+mod sieve;
+mod prime_counting;
 mod fibonacci;
 mod pentagonal;
 mod polygonal;
 mod hexagonal;
 
 pub use primes::*;
+pub use primality::*;
+pub use sieve::*;
+pub use prime_counting::*;
 
 pub use fibonacci::*;
 
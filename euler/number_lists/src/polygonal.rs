@@ -1,32 +1,103 @@
+use std::ops::Range;
+
 use integers::Integer;
 
-pub(crate) trait Polygonal: Integer {
-    fn polygonal_root(self, n: Self) -> Option<Self>;
-    fn is_polygonal(self, n: Self) -> bool;
-    fn as_polygonal(self, n: Self) -> Self;
+/// Figurate numbers, counting dots arranged in regular shapes. `self` is the index when
+/// building one, and the value when looking its index up.
+pub trait Figurate: Integer {
+    fn polygonal_root(self, sides: Self) -> Option<Self>;
+    fn is_polygonal(self, sides: Self) -> bool;
+    fn as_polygonal(self, sides: Self) -> Self;
+    fn centered_polygonal_root(self, sides: Self) -> Option<Self>;
+    fn as_centered_polygonal(self, sides: Self) -> Self;
+    fn tetrahedral_root(self) -> Option<Self>;
+    fn as_tetrahedral(self) -> Self;
+    fn square_pyramidal_root(self) -> Option<Self>;
+    fn as_square_pyramidal(self) -> Self;
 }
 
-impl<T: Integer> Polygonal for T {
-    fn polygonal_root(self, n: Self) -> Option<Self> {
-        let base = n - Self::two();
-        let base2 = base - Self::two();
-        let a = self * Self::from(8) * base + base2 * base2;
+impl<T: Integer> Figurate for T {
+    fn polygonal_root(self, sides: Self) -> Option<Self> {
+        // n = (√(8(s - 2)x + (s - 4)²) + s - 4) / 2(s - 2), with s - 4 negative for triangles
+        let base = sides - Self::two();
+        let offset = if sides >= Self::from(4) { sides - Self::from(4) } else { Self::from(4) - sides };
+        let a = self * Self::from(8) * base + offset * offset;
         let b = a.sqrt();
         if b * b != a { return None; }
-        let c = b + base2;
+        let c = b + sides - Self::from(4);
         let div = base * Self::two();
         if c % div == Self::zero() { Some(c / div) } else { None }
     }
 
-    fn is_polygonal(self, n: Self) -> bool {
-        self.polygonal_root(n).is_some()
+    fn is_polygonal(self, sides: Self) -> bool {
+        self.polygonal_root(sides).is_some()
+    }
+
+    fn as_polygonal(self, sides: Self) -> Self {
+        (sides - Self::two()) * (self * self - self) / Self::two() + self
+    }
+
+    fn centered_polygonal_root(self, sides: Self) -> Option<Self> {
+        // x = s * n(n - 1) / 2 + 1
+        if self < Self::one() { return None; }
+        let twice = (self - Self::one()) * Self::two();
+        if twice % sides != Self::zero() { return None; }
+        let a = twice / sides * Self::from(4) + Self::one();
+        let b = a.sqrt();
+        if b * b == a { Some((b + Self::one()) / Self::two()) } else { None }
+    }
+
+    fn as_centered_polygonal(self, sides: Self) -> Self {
+        sides * (self * self - self) / Self::two() + Self::one()
+    }
+
+    fn tetrahedral_root(self) -> Option<Self> {
+        cubic_root(self, 6.0, Self::as_tetrahedral)
     }
 
-    fn as_polygonal(self, n: Self) -> Self {
-        let n1 = n - Self::two();
-        let n2 = n1 - Self::two();
-        (n1 * self * self - n2 * self) / Self::two()
+    fn as_tetrahedral(self) -> Self {
+        self * (self + Self::one()) / Self::two() * (self + Self::two()) / Self::from(3)
     }
+
+    fn square_pyramidal_root(self) -> Option<Self> {
+        cubic_root(self, 3.0, Self::as_square_pyramidal)
+    }
+
+    fn as_square_pyramidal(self) -> Self {
+        self * (self + Self::one()) / Self::two() * (self * Self::two() + Self::one()) / Self::from(3)
+    }
+}
+
+/// Index of `value` in an increasing sequence growing like n³ / `growth`, 6 for tetrahedral and
+/// 3 for square pyramidal numbers. The floating point cube root lands within a step or two of
+/// it, stepping corrects the rest.
+fn cubic_root<T: Integer>(value: T, growth: f64, nth: impl Fn(T) -> T) -> Option<T> {
+    let estimate = (value.to_f64()? * growth).cbrt();
+    let mut n = T::from_f64(estimate)?;
+    while n > T::zero() && nth(n) > value { n -= T::one(); }
+    while nth(n + T::one()) <= value { n += T::one(); }
+    if nth(n) == value { Some(n) } else { None }
+}
+
+/// s-gonal numbers for any number of sides from 3.
+pub fn polygonals<T: Integer>(sides: T) -> PolygonalIterator<T> {
+    PolygonalIterator::new(sides)
+}
+
+pub fn centered_polygonals<T: Integer>(sides: T) -> impl Iterator<Item=T> {
+    indices().map(move |n: T| n.as_centered_polygonal(sides))
+}
+
+pub fn tetrahedrals<T: Integer>() -> impl Iterator<Item=T> {
+    indices().map(T::as_tetrahedral)
+}
+
+pub fn square_pyramidals<T: Integer>() -> impl Iterator<Item=T> {
+    indices().map(T::as_square_pyramidal)
+}
+
+fn indices<T: Integer>() -> impl Iterator<Item=T> {
+    std::iter::successors(Some(T::one()), |&n| Some(n + T::one()))
 }
 
 pub struct PolygonalIterator<T: Integer> {
@@ -39,6 +110,12 @@ impl<T: Integer> PolygonalIterator<T> {
     pub(crate) fn new(n: T) -> Self {
         Self { base: n - T::two(), last: T::zero(), total: T::zero() }
     }
+
+    /// The numbers within `range`, starting from the first one in it.
+    pub fn in_range(self, range: Range<T>) -> impl Iterator<Item=T> {
+        self.skip_while(move |&n| n < range.start)
+            .take_while(move |&n| n < range.end)
+    }
 }
 
 impl<T: Integer> Iterator for PolygonalIterator<T> {
@@ -50,3 +127,59 @@ impl<T: Integer> Iterator for PolygonalIterator<T> {
         Some(self.total)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generates_polygonal_numbers_of_any_order() {
+        for sides in 3..=12u64 {
+            for (n, x) in polygonals(sides).take(100).enumerate() {
+                let n = n as u64 + 1;
+                assert_eq!(n.as_polygonal(sides), x);
+                assert_eq!(x.polygonal_root(sides), Some(n));
+                assert_eq!((x + 1).is_polygonal(sides), (x + 1).polygonal_root(sides).is_some());
+            }
+        }
+        assert_eq!(polygonals(8).take(5).collect::<Vec<u32>>(), vec!(1, 8, 21, 40, 65));
+        assert_eq!(0.polygonal_root(3), Some(0));
+        assert_eq!(56.polygonal_root(3), None);
+    }
+
+    #[test]
+    fn limits_polygonals_to_a_range() {
+        let squares = polygonals(4u32).in_range(1000..10000).collect::<Vec<_>>();
+        assert_eq!(squares.len(), 68);
+        assert_eq!((squares[0], *squares.last().unwrap()), (1024, 9801));
+        assert_eq!(polygonals(3).in_range(10..11).collect::<Vec<u32>>(), vec!(10));
+    }
+
+    #[test]
+    fn generates_centered_polygonal_numbers() {
+        assert_eq!(centered_polygonals(6).take(5).collect::<Vec<u32>>(), vec!(1, 7, 19, 37, 61));
+        assert_eq!(centered_polygonals(4).take(5).collect::<Vec<u32>>(), vec!(1, 5, 13, 25, 41));
+        assert_eq!(61.centered_polygonal_root(6), Some(5));
+        assert_eq!(62.centered_polygonal_root(6), None);
+        assert_eq!(0.centered_polygonal_root(6), None);
+    }
+
+    #[test]
+    fn generates_solid_figurate_numbers() {
+        assert_eq!(tetrahedrals().take(6).collect::<Vec<u32>>(), vec!(1, 4, 10, 20, 35, 56));
+        assert_eq!(square_pyramidals().take(6).collect::<Vec<u32>>(), vec!(1, 5, 14, 30, 55, 91));
+        for (n, x) in tetrahedrals::<u64>().zip(1..).take(1000) {
+            assert_eq!(n.tetrahedral_root(), Some(x));
+            assert_eq!((n + 1).tetrahedral_root(), None);
+        }
+        assert_eq!(338350.square_pyramidal_root(), Some(100));
+        assert_eq!(338351.square_pyramidal_root(), None);
+        assert_eq!(0.tetrahedral_root(), Some(0));
+        assert_eq!(1_000_000u64.as_tetrahedral().tetrahedral_root(), Some(1_000_000));
+        assert_eq!(1_000_000u64.as_square_pyramidal().square_pyramidal_root(), Some(1_000_000));
+        let n = 1u128 << 40;
+        assert_eq!(n.as_tetrahedral().tetrahedral_root(), Some(n));
+        assert_eq!((n.as_tetrahedral() - 1).tetrahedral_root(), None);
+        assert_eq!(n.as_square_pyramidal().square_pyramidal_root(), Some(n));
+    }
+}
@@ -0,0 +1,49 @@
+use integers::Integer;
+use integers::primality::{is_prime_u128, is_prime_u64};
+
+use crate::PrimeSieve;
+
+pub trait Primality: Integer {
+    /// Trial division by small primes, then Miller–Rabin. Exact for every value up to
+    /// 3.3 * 10^24, which covers all of u64; larger u128 values go through Baillie–PSW, which
+    /// no known number fools but which is not proven exact.
+    fn is_prime(&self) -> bool {
+        match (self.to_u64(), self.to_u128()) {
+            (Some(n), _) => is_prime_u64(n),
+            (None, Some(n)) => is_prime_u128(n),
+            _ => false,
+        }
+    }
+
+    /// Answers from the sieve when it reaches `self`.
+    fn is_prime_with(&self, sieve: &PrimeSieve) -> bool {
+        match self.to_usize() {
+            Some(n) if n <= sieve.max() => sieve.is_prime(n),
+            _ => self.is_prime(),
+        }
+    }
+}
+
+impl<T: Integer> Primality for T {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn agrees_with_the_sieve() {
+        let sieve = PrimeSieve::new(100_000);
+        for n in 0..=100_000u32 {
+            assert_eq!(n.is_prime(), sieve.is_prime(n as usize), "{n}");
+        }
+        assert!(!(-7i32).is_prime());
+    }
+
+    #[test]
+    fn uses_the_sieve_when_it_can() {
+        let sieve = PrimeSieve::new(1000);
+        assert!(997u32.is_prime_with(&sieve));
+        assert!(!999u32.is_prime_with(&sieve));
+        assert!(1_000_003u64.is_prime_with(&sieve));
+    }
+}
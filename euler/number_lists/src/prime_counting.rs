@@ -0,0 +1,70 @@
+use integers::Integer;
+
+/// Number of primes up to `n`.
+pub fn prime_count(n: u64) -> u64 {
+    lucy_hedgehog(n, |v| v.saturating_sub(1), |_| 1)
+}
+
+/// Sum of the primes up to `n`.
+pub fn prime_sum(n: u64) -> u128 {
+    lucy_hedgehog(n, |v| { let v = v as u128; (v * (v + 1) / 2).saturating_sub(1) }, |p| p as u128)
+}
+
+/// Lucy_Hedgehog's method, summing a completely multiplicative `weight` over the primes up to
+/// `n` in O(n^(3/4)) time and O(√n) memory. `total(v)` is the sum of the weights of 2..=v.
+///
+/// S(v, p) is the sum over 2..=v of the numbers that are prime or have no prime factor up to
+/// p. Sieving p out, S(v, p) = S(v, p - 1) - w(p) * (S(v / p, p - 1) - S(p - 1, p - 1)), and
+/// only the values n / i are ever needed.
+fn lucy_hedgehog<T: Integer>(n: u64, total: impl Fn(u64) -> T, weight: impl Fn(u64) -> T) -> T {
+    let r = n.isqrt();
+    // small[v] = S(v) for v <= r, large[i] = S(n / i) for i <= r
+    let mut small: Vec<T> = (0..=r).map(&total).collect();
+    let mut large: Vec<T> = (0..=r).map(|i| n.checked_div(i).map_or(T::zero(), &total)).collect();
+    for p in 2..=r {
+        if small[p as usize] == small[p as usize - 1] { continue; }
+        let below = small[p as usize - 1];
+        let w = weight(p);
+        let square = p * p;
+        for i in 1..=r.min(n / square) {
+            let d = i * p;
+            let s = if d <= r { large[d as usize] } else { small[(n / d) as usize] };
+            large[i as usize] -= w * (s - below);
+        }
+        for v in (square..=r).rev() {
+            let s = small[(v / p) as usize];
+            small[v as usize] -= w * (s - below);
+        }
+    }
+    if n == 0 { T::zero() } else { large[1] }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::PrimeSieve;
+
+    #[test]
+    fn counts_primes() {
+        let sieve = PrimeSieve::new(2000);
+        let mut count = 0;
+        for n in 0..=2000 {
+            if sieve.is_prime(n) { count += 1; }
+            assert_eq!(prime_count(n as u64), count, "{n}");
+        }
+        assert_eq!(prime_count(1_000_000), 78_498);
+        assert_eq!(prime_count(1_000_000_000), 50_847_534);
+    }
+
+    #[test]
+    fn sums_primes() {
+        let sieve = PrimeSieve::new(2000);
+        let mut sum = 0;
+        for n in 0..=2000 {
+            if sieve.is_prime(n) { sum += n as u128; }
+            assert_eq!(prime_sum(n as u64), sum, "{n}");
+        }
+        assert_eq!(prime_sum(2_000_000), 142_913_828_922);
+        assert_eq!(prime_sum(1_000_000_000), 24_739_512_092_254_535);
+    }
+}
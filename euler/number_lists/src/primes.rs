@@ -1,19 +1,11 @@
 use std::fmt::{Debug, Formatter};
 use integers::Integer;
 
+use crate::{Primality, PrimeSieve};
+
 pub fn prime_sieve(max: usize) -> Vec<bool> {
-    let mut primes = vec!();
-    primes.resize(max+1, true);
-    for q in (4..=max).step_by(2) {
-        primes[q] = false;
-    }
-    for p in (3..=max).step_by(2) {
-        if !primes[p] { continue; }
-        for q in (p+p..=max).step_by(p) {
-            primes[q] = false;
-        }
-    }
-    primes
+    let sieve = PrimeSieve::new(max);
+    (0..=max).map(|n| sieve.is_prime(n)).collect()
 }
 
 pub fn prime_iterator<T: Integer>() -> PrimeIterator<T> {
@@ -35,8 +27,12 @@ impl <T: Integer> PrimeIterator<T> {
         Self { primes: vec!() }
     }
 
+    /// Looks `n` up among the primes found so far, tests it directly past them.
     pub fn is_prime(&self, n: T) -> bool {
-        self.primes.contains(&n)
+        match self.primes.last() {
+            Some(&last) if n <= last => self.primes.binary_search(&n).is_ok(),
+            _ => n.is_prime(),
+        }
     }
 
     fn next_is_prime(&self, n: T) -> bool {
@@ -62,3 +58,23 @@ impl <T: Integer> Iterator for PrimeIterator<T> {
         Some(*self.primes.last().unwrap())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sieves_booleans() {
+        assert_eq!(prime_sieve(7), vec!(false, false, true, true, false, true, false, true));
+    }
+
+    #[test]
+    fn knows_primes_the_iterator_has_not_reached() {
+        let mut primes = prime_iterator::<u64>();
+        assert_eq!(primes.nth(3), Some(7));
+        assert!(primes.is_prime(5));
+        assert!(!primes.is_prime(6));
+        assert!(primes.is_prime(104_729));
+        assert!(!primes.is_prime(104_730));
+    }
+}
@@ -0,0 +1,173 @@
+use std::ops::Range;
+
+use crate::Primality;
+
+// odd numbers sieved at once, 32 KiB of bits to stay in the L1 cache
+const SEGMENT_LENGTH: usize = 1 << 18;
+
+/// Sieve of Eratosthenes answering primality up to `max`. Only odd numbers are kept, one bit
+/// each, and they are crossed off one segment at a time.
+pub struct PrimeSieve {
+    max: usize,
+    // bit i set when 2i + 1 is composite
+    composites: Vec<u64>,
+}
+
+impl PrimeSieve {
+    pub fn new(max: usize) -> Self {
+        let length = max.div_ceil(2);
+        let base = odd_primes_up_to((max as u64).isqrt());
+        let mut composites = Vec::with_capacity(length.div_ceil(64));
+        for start in (0..length).step_by(SEGMENT_LENGTH) {
+            let segment = sieve_segment(2 * start as u64 + 1, SEGMENT_LENGTH.min(length - start), &base);
+            composites.extend(segment);
+        }
+        Self { max, composites }
+    }
+
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Falls back to Miller–Rabin above `max`.
+    pub fn is_prime(&self, n: usize) -> bool {
+        if n > self.max { return n.is_prime() }
+        if n.is_multiple_of(2) { return n == 2 }
+        self.composites[n / 64 / 2] & 1 << (n / 2 % 64) == 0
+    }
+
+    pub fn primes(&self) -> impl Iterator<Item=usize> + '_ {
+        let two = if self.max >= 2 { Some(2) } else { None };
+        two.into_iter().chain(unmarked(&self.composites, self.max.div_ceil(2)).map(|i| 2 * i + 1))
+    }
+}
+
+/// Primes in `range` in increasing order, sieved a segment at a time as the iterator advances.
+/// Memory stays around the square root of the end of the range whatever its length.
+pub fn primes_in(range: Range<u64>) -> PrimesIn {
+    let low = range.start.max(3) | 1;
+    PrimesIn {
+        base: odd_primes_up_to(range.end.saturating_sub(1).isqrt()),
+        two: range.contains(&2),
+        low,
+        end: range.end,
+        segment: vec!().into_iter(),
+    }
+}
+
+pub struct PrimesIn {
+    base: Vec<u64>,
+    two: bool,
+    // next odd number to sieve
+    low: u64,
+    end: u64,
+    segment: std::vec::IntoIter<u64>,
+}
+
+impl Iterator for PrimesIn {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.two {
+            self.two = false;
+            return Some(2);
+        }
+        loop {
+            if let Some(p) = self.segment.next() { return Some(p) }
+            if self.low >= self.end { return None }
+            let length = (SEGMENT_LENGTH as u64).min((self.end - self.low).div_ceil(2)) as usize;
+            let low = self.low;
+            self.segment = unmarked(&sieve_segment(low, length, &self.base), length)
+                .map(|i| low + 2 * i as u64)
+                .collect::<Vec<_>>()
+                .into_iter();
+            self.low = self.low.saturating_add(2 * length as u64);
+        }
+    }
+}
+
+/// Crosses off the odd multiples of `base` among the `length` odd numbers from `low`, which is
+/// odd. Bit i stands for low + 2i and is set when that number is composite, or 1.
+fn sieve_segment(low: u64, length: usize, base: &[u64]) -> Vec<u64> {
+    let mut composites = vec!(0u64; length.div_ceil(64));
+    let high = low + 2 * length as u64;
+    for &p in base {
+        if p * p >= high { break; }
+        let mut multiple = (p * p).max(low.div_ceil(p) * p);
+        if multiple.is_multiple_of(2) { multiple += p; }
+        let mut i = ((multiple - low) / 2) as usize;
+        while i < length {
+            composites[i / 64] |= 1 << (i % 64);
+            i += p as usize;
+        }
+    }
+    if low == 1 && length > 0 { composites[0] |= 1; }
+    composites
+}
+
+/// Indices below `length` of the bits left clear.
+fn unmarked(composites: &[u64], length: usize) -> impl Iterator<Item=usize> + '_ {
+    composites.iter().enumerate()
+        .flat_map(|(k, &word)| {
+            let mut left = !word;
+            std::iter::from_fn(move || {
+                if left == 0 { return None }
+                let i = 64 * k + left.trailing_zeros() as usize;
+                left &= left - 1;
+                Some(i)
+            })
+        })
+        .take_while(move |&i| i < length)
+}
+
+/// Odd primes up to `max`, the base primes of a segmented sieve.
+fn odd_primes_up_to(max: u64) -> Vec<u64> {
+    let length = max.div_ceil(2) as usize;
+    let mut composite = vec!(false; length);
+    let mut primes = vec!();
+    for i in 1..length {
+        if composite[i] { continue; }
+        let p = 2 * i + 1;
+        primes.push(p as u64);
+        for j in (p * p / 2..length).step_by(p) {
+            composite[j] = true;
+        }
+    }
+    primes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sieves_primes() {
+        let sieve = PrimeSieve::new(30);
+        assert_eq!(sieve.primes().collect::<Vec<_>>(), vec!(2, 3, 5, 7, 11, 13, 17, 19, 23, 29));
+        assert!(!sieve.is_prime(0) && !sieve.is_prime(1));
+        assert!(sieve.is_prime(31));
+        assert_eq!(PrimeSieve::new(1).primes().count(), 0);
+        assert_eq!(PrimeSieve::new(2).primes().collect::<Vec<_>>(), vec!(2));
+    }
+
+    #[test]
+    fn sieves_across_segments() {
+        let max = 3 * SEGMENT_LENGTH + 12_345;
+        let sieve = PrimeSieve::new(max);
+        assert_eq!(sieve.primes().count(), 63_869);
+        assert!((0..=max).step_by(7).all(|n| sieve.is_prime(n) == n.is_prime()));
+        assert!(sieve.primes().map(|p| p as u64).eq(primes_in(0..max as u64 + 1)));
+    }
+
+    #[test]
+    fn iterates_over_primes_in_a_range() {
+        assert_eq!(primes_in(0..20).collect::<Vec<_>>(), vec!(2, 3, 5, 7, 11, 13, 17, 19));
+        assert_eq!(primes_in(2..3).collect::<Vec<_>>(), vec!(2));
+        assert_eq!(primes_in(14..17).count(), 0);
+        assert_eq!(
+            primes_in(1_000_000_000_000..1_000_000_000_100).collect::<Vec<_>>(),
+            vec!(1_000_000_000_039, 1_000_000_000_061, 1_000_000_000_063, 1_000_000_000_091)
+        );
+        assert_eq!(primes_in(1_000_000_000_000..1_000_010_000_000).count(), 361_726);
+    }
+}
@@ -0,0 +1,203 @@
+use num_integer::Integer as _;
+
+use integers::Integer;
+use integers::factorization::SmallestPrimeFactors;
+
+/// Arithmetic functions of positive integers, computed from their prime factors.
+pub trait Arithmetic: Integer {
+    /// Sum of the k-th powers of the divisors.
+    /// ```rust
+    /// use number_theory::Arithmetic;
+    ///
+    /// assert_eq!(12.sigma(0), 6);
+    /// assert_eq!(12.sigma(1), 28);
+    /// assert_eq!(12.sigma(2), 210);
+    /// ```
+    /// Panics for zero and negative numbers, whose divisors are not a finite positive set.
+    fn sigma(&self, k: u32) -> Self {
+        assert!(*self > Self::zero(), "sigma is only defined for positive integers");
+        self.factorize().factors()
+            .map(|(p, e)| {
+                let pk = pow(p, k);
+                let mut term = Self::one();
+                let mut sum = Self::one();
+                for _ in 0..e.to_u32().unwrap() {
+                    term *= pk;
+                    sum += term;
+                }
+                sum
+            })
+            .fold(Self::one(), |product, sum| product * sum)
+    }
+
+    /// Number of divisors.
+    /// ```rust
+    /// use number_theory::Arithmetic;
+    ///
+    /// assert_eq!(1.tau(), 1);
+    /// assert_eq!(28.tau(), 6);
+    /// ```
+    fn tau(&self) -> Self {
+        self.sigma(0)
+    }
+
+    /// Euler's totient, how many numbers up to `self` are coprime with it.
+    /// ```rust
+    /// use number_theory::Arithmetic;
+    ///
+    /// assert_eq!(1.totient(), 1);
+    /// assert_eq!(36.totient(), 12);
+    /// assert_eq!(97.totient(), 96);
+    /// ```
+    fn totient(&self) -> Self {
+        self.factorize().factors()
+            .fold(*self, |phi, (p, _)| phi / p * (p - Self::one()))
+    }
+
+    /// Möbius function, 0 when a square divides `self`, otherwise -1 to the number of prime
+    /// factors.
+    /// ```rust
+    /// use number_theory::Arithmetic;
+    ///
+    /// assert_eq!(1.mobius(), 1);
+    /// assert_eq!(30.mobius(), -1);
+    /// assert_eq!(18.mobius(), 0);
+    /// ```
+    fn mobius(&self) -> i8 {
+        let factorized = self.factorize();
+        if factorized.factors().any(|(_, e)| e > Self::one()) { return 0 }
+        if factorized.distinct_count().is_multiple_of(2) { 1 } else { -1 }
+    }
+
+    /// Carmichael function, the smallest m with a^m ≡ 1 modulo `self` for every a coprime with
+    /// it.
+    /// ```rust
+    /// use number_theory::Arithmetic;
+    ///
+    /// assert_eq!(1.carmichael(), 1);
+    /// assert_eq!(8.carmichael(), 2);
+    /// assert_eq!(561.carmichael(), 80);
+    /// ```
+    fn carmichael(&self) -> Self {
+        self.factorize().factors()
+            .map(|(p, e)| prime_power_carmichael(p, e.to_u32().unwrap()))
+            .fold(Self::one(), |lambda, l| lambda.lcm(&l))
+    }
+
+    /// Product of the distinct prime factors.
+    /// ```rust
+    /// use number_theory::Arithmetic;
+    ///
+    /// assert_eq!(1.radical(), 1);
+    /// assert_eq!(504.radical(), 42);
+    /// ```
+    fn radical(&self) -> Self {
+        self.factorize().factors().fold(Self::one(), |product, (p, _)| product * p)
+    }
+}
+
+impl<T: Integer> Arithmetic for T {}
+
+fn pow<T: Integer>(base: T, exponent: u32) -> T {
+    (0..exponent).fold(T::one(), |power, _| power * base)
+}
+
+fn prime_power_carmichael<T: Integer>(p: T, e: u32) -> T {
+    let phi = pow(p, e - 1) * (p - T::one());
+    if p == T::two() && e >= 3 { phi / T::two() } else { phi }
+}
+
+/// σ_k of every number up to `max`, index 0 left at 0.
+pub fn sigma_up_to(max: usize, k: u32) -> Vec<u64> {
+    multiplicative_up_to(max, |p, e, _| {
+        let pk = pow(p as u64, k);
+        (0..e).fold((1, 1), |(sum, term), _| (sum + term * pk, term * pk)).0
+    })
+}
+
+pub fn tau_up_to(max: usize) -> Vec<u64> {
+    multiplicative_up_to(max, |_, e, _| e as u64 + 1)
+}
+
+pub fn totients_up_to(max: usize) -> Vec<u64> {
+    multiplicative_up_to(max, |p, _, power| (power / p * (p - 1)) as u64)
+}
+
+pub fn mobius_up_to(max: usize) -> Vec<i8> {
+    multiplicative_up_to(max, |_, e, _| if e == 1 { -1 } else { 0 })
+}
+
+pub fn radicals_up_to(max: usize) -> Vec<u64> {
+    multiplicative_up_to(max, |p, _, _| p as u64)
+}
+
+pub fn carmichael_up_to(max: usize) -> Vec<u64> {
+    combined_up_to(max, |p, e, _| prime_power_carmichael(p as u64, e), |a, b| a.lcm(&b))
+}
+
+/// Values of a multiplicative function for every number up to `max`, from its values on prime
+/// powers `f(p, e, p^e)`.
+fn multiplicative_up_to<V>(max: usize, prime_power: impl Fn(usize, u32, usize) -> V) -> Vec<V>
+where V: Copy + Default + num_traits::One {
+    combined_up_to(max, prime_power, |a, b| a * b)
+}
+
+/// Splits every n into p^e * m with p its smallest prime factor, m smaller and coprime with p,
+/// and combines the value on p^e with the one already known for m. Near-linear in `max`.
+fn combined_up_to<V>(max: usize, prime_power: impl Fn(usize, u32, usize) -> V, combine: impl Fn(V, V) -> V) -> Vec<V>
+where V: Copy + Default + num_traits::One {
+    let factors = SmallestPrimeFactors::new(max);
+    let mut values = vec!(V::default(); max + 1);
+    if max >= 1 { values[1] = V::one(); }
+    for n in 2..=max {
+        let p = factors.smallest(n);
+        let (mut m, mut e, mut power) = (n / p, 1, p);
+        while m.is_multiple_of(p) {
+            m /= p;
+            e += 1;
+            power *= p;
+        }
+        values[n] = combine(values[m], prime_power(p, e, power));
+    }
+    values
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sieves_agree_with_factorizations() {
+        let max = 2000;
+        let sigma = sigma_up_to(max, 1);
+        let sigma2 = sigma_up_to(max, 2);
+        let tau = tau_up_to(max);
+        let totients = totients_up_to(max);
+        let mobius = mobius_up_to(max);
+        let radicals = radicals_up_to(max);
+        let carmichael = carmichael_up_to(max);
+        for n in 1..=max as u64 {
+            let i = n as usize;
+            assert_eq!(sigma[i], n.sigma(1), "{n}");
+            assert_eq!(sigma2[i], n.sigma(2), "{n}");
+            assert_eq!(tau[i], n.tau(), "{n}");
+            assert_eq!(totients[i], n.totient(), "{n}");
+            assert_eq!(mobius[i], n.mobius(), "{n}");
+            assert_eq!(radicals[i], n.radical(), "{n}");
+            assert_eq!(carmichael[i], n.carmichael(), "{n}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "positive integers")]
+    fn refuses_the_divisor_sum_of_zero() {
+        0u32.sigma(1);
+    }
+
+    #[test]
+    fn totient_counts_coprime_numbers() {
+        for n in 1..200u32 {
+            assert_eq!(n.totient() as usize, (1..=n).filter(|k| k.gcd(&n) == 1).count());
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use integers::Integer;
+use integers::biguint::BigUint;
+
+/// Continued fraction [initial; period, period, ...] repeating its period forever, or ending
+/// after the initial term when the period is empty.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeriodicContinuedFraction<T> {
+    pub initial: T,
+    pub period: Vec<T>,
+}
+
+impl<T: Integer> PeriodicContinuedFraction<T> {
+    pub fn terms(&self) -> impl Iterator<Item=T> + '_ {
+        std::iter::once(self.initial).chain(self.period.iter().copied().cycle())
+    }
+
+    pub fn convergents(&self) -> Convergents<impl Iterator<Item=T> + '_> {
+        convergents(self.terms())
+    }
+}
+
+/// Successive convergents h / k of the continued fraction with the given non-negative terms,
+/// as exact fractions in lowest terms.
+pub fn convergents<T: Integer, I: IntoIterator<Item=T>>(terms: I) -> Convergents<I::IntoIter> {
+    Convergents {
+        terms: terms.into_iter(),
+        numerators: (BigUint::zero(), BigUint::one()),
+        denominators: (BigUint::one(), BigUint::zero()),
+    }
+}
+
+pub struct Convergents<I> {
+    terms: I,
+    // h(n-2), h(n-1) and k(n-2), k(n-1)
+    numerators: (BigUint, BigUint),
+    denominators: (BigUint, BigUint),
+}
+
+impl<T: Integer, I: Iterator<Item=T>> Iterator for Convergents<I> {
+    type Item = (BigUint, BigUint);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let term = BigUint::from(self.terms.next()?.to_u128().expect("negative continued fraction term"));
+        let numerator = &term * &self.numerators.1 + &self.numerators.0;
+        let denominator = &term * &self.denominators.1 + &self.denominators.0;
+        self.numerators = (std::mem::take(&mut self.numerators.1), numerator.clone());
+        self.denominators = (std::mem::take(&mut self.denominators.1), denominator.clone());
+        Some((numerator, denominator))
+    }
+}
+
+pub trait ContinuedFractions: Integer {
+    /// Continued fraction of √self, whose period ends with twice the initial term.
+    /// ```rust
+    /// use number_theory::ContinuedFractions;
+    ///
+    /// let sqrt23 = 23.sqrt_continued_fraction();
+    /// assert_eq!((sqrt23.initial, sqrt23.period), (4, vec!(1, 3, 1, 8)));
+    /// assert!(16.sqrt_continued_fraction().period.is_empty());
+    /// ```
+    fn sqrt_continued_fraction(&self) -> PeriodicContinuedFraction<Self> {
+        let d = *self;
+        let initial = d.sqrt();
+        let mut period = vec!();
+        if initial * initial == d { return PeriodicContinuedFraction { initial, period } }
+        // √d = a + (√d - m) / q with q dividing d - m² at every step
+        let (mut m, mut q, mut a) = (Self::zero(), Self::one(), initial);
+        while a != initial * Self::two() {
+            m = q * a - m;
+            q = (d - m * m) / q;
+            a = (initial + m) / q;
+            period.push(a);
+        }
+        PeriodicContinuedFraction { initial, period }
+    }
+
+    /// Smallest positive solution of Pell's equation x² - self * y² = 1, none when self is a
+    /// square.
+    /// ```rust
+    /// use number_theory::ContinuedFractions;
+    ///
+    /// let (x, y) = 61.pell_fundamental().unwrap();
+    /// assert_eq!((x.to_string(), y.to_string()), ("1766319049".to_string(), "226153980".to_string()));
+    /// assert!(9.pell_fundamental().is_none());
+    /// ```
+    fn pell_fundamental(&self) -> Option<(BigUint, BigUint)> {
+        let expansion = self.sqrt_continued_fraction();
+        let r = expansion.period.len();
+        if r == 0 { return None }
+        // the convergent ending the first period, or the second one when the period is odd
+        let index = if r.is_multiple_of(2) { r - 1 } else { 2 * r - 1 };
+        let mut convergents = expansion.convergents();
+        convergents.nth(index)
+    }
+
+    /// Every positive solution of x² - self * y² = 1, in increasing order.
+    fn pell_solutions(&self) -> PellSolutions {
+        let d = BigUint::from(self.to_u128().expect("negative Pell coefficient"));
+        let fundamental = self.pell_fundamental();
+        PellSolutions { d, fundamental: fundamental.clone(), current: fundamental }
+    }
+}
+
+impl<T: Integer> ContinuedFractions for T {}
+
+/// Powers of the fundamental solution, (x + y√d)^n.
+pub struct PellSolutions {
+    d: BigUint,
+    fundamental: Option<(BigUint, BigUint)>,
+    current: Option<(BigUint, BigUint)>,
+}
+
+impl Iterator for PellSolutions {
+    type Item = (BigUint, BigUint);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x1, y1) = self.fundamental.as_ref()?;
+        let (x, y) = self.current.take()?;
+        self.current = Some((x1 * &x + &self.d * y1 * &y, x1 * &y + y1 * &x));
+        Some((x, y))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn expands_square_roots() {
+        assert_eq!(2.sqrt_continued_fraction().period, vec!(2));
+        assert_eq!(13.sqrt_continued_fraction().period, vec!(1, 1, 1, 1, 6));
+        let odd_periods = (2..=10_000u32).filter(|d| d.sqrt_continued_fraction().period.len() % 2 == 1).count();
+        assert_eq!(odd_periods, 1322);
+    }
+
+    #[test]
+    fn computes_convergents() {
+        let sqrt2 = 2.sqrt_continued_fraction().convergents()
+            .take(4)
+            .map(|(h, k)| (h.to_u64().unwrap(), k.to_u64().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(sqrt2, vec!((1, 1), (3, 2), (7, 5), (17, 12)));
+        // e = [2; 1, 2, 1, 1, 4, 1, 1, 6, ...]
+        let e = (0..100u32).map(|i| match i {
+            0 => 2,
+            i if i % 3 == 2 => 2 * (i / 3 + 1),
+            _ => 1,
+        });
+        assert_eq!(convergents(e).nth(99).unwrap().0.digit_sum(), 272);
+    }
+
+    #[test]
+    fn solves_pell_equations() {
+        let (x, y) = 13u32.pell_fundamental().unwrap();
+        assert_eq!((x.to_u64(), y.to_u64()), (Some(649), Some(180)));
+        let solutions = 2u32.pell_solutions()
+            .take(4)
+            .map(|(x, y)| (x.to_u64().unwrap(), y.to_u64().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(solutions, vec!((3, 2), (17, 12), (99, 70), (577, 408)));
+        let d = BigUint::from(991u32);
+        for (x, y) in 991u32.pell_solutions().take(5) {
+            assert_eq!(&x * &x, &d * &y * &y + BigUint::one());
+        }
+        assert_eq!(4u32.pell_solutions().next(), None);
+    }
+}
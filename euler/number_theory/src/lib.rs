@@ -1,5 +1,9 @@
+mod arithmetic;
+mod continued_fraction;
 mod perfect;
 mod lychrel;
 
+pub use arithmetic::*;
+pub use continued_fraction::*;
 pub use perfect::*;
-pub use lychrel::*;
\ No newline at end of file
+pub use lychrel::*;
@@ -3,6 +3,8 @@ use std::cmp::Ordering;
 use integers::Integer;
 use PerfectVariant::*;
 
+use crate::Arithmetic;
+
 #[derive(PartialEq, Debug)]
 pub enum PerfectVariant<T> {
     Perfect(T),
@@ -11,8 +13,10 @@ pub enum PerfectVariant<T> {
 }
 
 pub trait Perfect: Integer {
+    /// Compares a number with the sum of its proper divisors. Panics for zero and negative
+    /// numbers, which have no finite divisor sum, see [`Arithmetic::sigma`].
     fn perfect_variant(&self) -> PerfectVariant<Self> {
-        let divisor_sum: Self = self.proper_divisors().into_iter().sum();
+        let divisor_sum = self.sigma(1) - *self;
         match divisor_sum.cmp(self) {
             Ordering::Equal => Perfect(*self),
             Ordering::Greater => Abundant(*self, divisor_sum),
@@ -33,6 +37,8 @@ pub trait Perfect: Integer {
     }
 
     fn amicable_pair(&self) -> Option<Self> {
+        // 1 has no proper divisors, and 0 no divisor sum to pair it with
+        if *self <= Self::one() { return None }
         match self.perfect_variant() {
             Perfect(_) => None,
             Abundant(_, first) => match first.perfect_variant() {
@@ -49,4 +55,25 @@ pub trait Perfect: Integer {
     }
 }
 
-impl<T: Integer> Perfect for T {}
\ No newline at end of file
+impl<T: Integer> Perfect for T {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pairs_amicable_numbers() {
+        assert_eq!(220u32.amicable_pair(), Some(284));
+        assert_eq!(284u32.amicable_pair(), Some(220));
+        assert_eq!(28u32.amicable_pair(), None);
+        assert_eq!(1u32.amicable_pair(), None);
+        assert_eq!(0u32.amicable_pair(), None);
+        assert_eq!(12u32.perfect_variant(), Abundant(12, 16));
+    }
+
+    #[test]
+    #[should_panic(expected = "positive integers")]
+    fn has_no_variant_for_zero() {
+        0u32.perfect_variant();
+    }
+}
@@ -54,6 +54,8 @@ mod p0053;
 mod p0054;
 mod p0055;
 mod p0056;
+mod p0057;
+mod p0061;
 
 use std::env;
 use std::process::Command;
@@ -1,11 +1,11 @@
-use number_lists::prime_iterator;
+use number_lists::Primality;
 
 pub fn p0007_solver() -> String {
     nth_prime(10_001).to_string()
 }
 
 fn nth_prime(n: usize) -> u64 {
-    prime_iterator::<u64>().nth(n-1).unwrap()
+    (2u64..).filter(Primality::is_prime).nth(n-1).unwrap()
 }
 
 #[test]
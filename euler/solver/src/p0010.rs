@@ -1,13 +1,11 @@
-use number_lists::prime_iterator;
+use number_lists::prime_sum;
 
 pub fn p0010_solver() -> String {
     summation_of_primes(2_000_000).to_string()
 }
 
 fn summation_of_primes(max: u64) -> u64 {
-    prime_iterator::<u64>()
-        .take_while(|&n| n < max)
-        .sum()
+    prime_sum(max - 1) as u64
 }
 
 #[test]
@@ -7,7 +7,7 @@ pub fn p0012_solver() -> String {
 
 fn highly_divisible_triangular_number(min_divisor_count: usize) -> u64 {
     triangulars::<u64>()
-        .find(|t| t.proper_divisors().len() >= min_divisor_count)
+        .find(|t| t.factorize().divisor_count() > min_divisor_count)
         .unwrap()
 }
 
@@ -1,12 +1,12 @@
-use itertools::Itertools;
+use integers::biguint::BigUint;
 
 pub fn p0013_solver() -> String {
-    large_sum().to_string().chars().take(10).join("")
+    large_sum().to_string()[..10].to_string()
 }
 
-fn large_sum() -> u128 {
+fn large_sum() -> BigUint {
     NUMBERS.lines()
-        .filter_map(|l| l.chars().take(20).join("").parse::<u128>().ok())
+        .filter_map(|l| l.parse::<BigUint>().ok())
         .sum()
 }
 
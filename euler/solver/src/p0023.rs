@@ -1,22 +1,24 @@
-use std::collections::HashSet;
-use number_theory::Perfect;
+use number_theory::sigma_up_to;
 
 pub fn p0023_solver() -> String {
     non_abundant_sums().to_string()
 }
 
 fn non_abundant_sums() -> u64 {
+    let sigma = sigma_up_to(ABUNDANT_SUM_LIMIT, 1);
     let abundant_numbers = (12..=ABUNDANT_SUM_LIMIT)
-        .filter(|&n| n.is_abundant())
-        .collect::<Vec<u64>>();
-    let abundant_sums = (0..abundant_numbers.len())
-        .flat_map(|a| (a..abundant_numbers.len()).map(move |b| (a,b)))
-        .map(|(a,b)| abundant_numbers[a] + abundant_numbers[b])
-        .filter(|&s| s <= ABUNDANT_SUM_LIMIT)
-        .collect::<HashSet<u64>>();
+        .filter(|&n| sigma[n] - n as u64 > n as u64)
+        .collect::<Vec<usize>>();
+    let mut abundant_sums = vec!(false; ABUNDANT_SUM_LIMIT + 1);
+    for (i, &a) in abundant_numbers.iter().enumerate() {
+        for &b in abundant_numbers[i..].iter().take_while(|&&b| a + b <= ABUNDANT_SUM_LIMIT) {
+            abundant_sums[a + b] = true;
+        }
+    }
     (1..=ABUNDANT_SUM_LIMIT)
-        .filter(|n| !abundant_sums.contains(n))
+        .filter(|&n| !abundant_sums[n])
+        .map(|n| n as u64)
         .sum()
 }
 
-static ABUNDANT_SUM_LIMIT: u64 = 28123;
+static ABUNDANT_SUM_LIMIT: usize = 28123;
@@ -1,16 +1,15 @@
-use itertools::Itertools;
+use algorithms::combinatorics::nth_permutation;
 
 pub fn p0024_solver() -> String {
     lexicographic_permutations("0123456789", 1_000_000)
 }
 
 fn lexicographic_permutations(digits: &str, nth: usize) -> String {
-    digits.chars()
-        .permutations(digits.len())
-        .nth(nth-1)
+    let digits = digits.chars().collect::<Vec<_>>();
+    nth_permutation(&digits, nth as u128 - 1)
         .unwrap()
         .iter()
-        .join("")
+        .collect()
 }
 
 #[test]
@@ -1,22 +1,19 @@
+use integers::biguint::BigUint;
+
 pub fn p0025_solver() -> String {
     n_digit_fibonacci_number(1000).to_string()
 }
 
 fn n_digit_fibonacci_number(n: usize) -> usize {
-    let mut stripped = 0u32;
-    let mut a = 1u64;
-    let mut b = 1u64;
+    let smallest = BigUint::from(10u32).pow(n as u32 - 1);
+    let mut a = BigUint::one();
+    let mut b = BigUint::one();
     let mut i = 2;
-    while b.ilog10() + stripped < n as u32 - 1 {
-        let c = a + b;
+    while b < smallest {
+        let c = &a + &b;
         a = b;
         b = c;
         i += 1;
-        if a > 1000000 {
-            a /= 10;
-            b /= 10;
-            stripped += 1;
-        }
     }
     i
 }
@@ -1,12 +1,12 @@
-use algorithms::dynamic_programming::dynamic_programming;
+use algorithms::dynamic_programming::ways;
 
 pub fn p0031_solver() -> String {
     coin_sums(200).to_string()
 }
 
-fn coin_sums(target: usize) -> usize {
+fn coin_sums(target: usize) -> u64 {
     let coin_values = vec!(1, 2, 5, 10, 20, 50, 100, 200);
-    dynamic_programming(&coin_values, target)
+    ways(&coin_values, target)
 }
 
 #[test]
@@ -1,5 +1,5 @@
 use integers::Integer;
-use number_lists::prime_sieve;
+use number_lists::PrimeSieve;
 
 pub fn p0035_solver() -> String {
     circular_primes(1_000_000).count().to_string()
@@ -10,14 +10,14 @@ fn circular_primes(max: usize) -> impl Iterator<Item=usize> {
 }
 
 struct CircularPrimeIterator {
-    prime_sieve: Vec<bool>,
+    prime_sieve: PrimeSieve,
     current_index: usize,
 }
 
 impl CircularPrimeIterator {
     fn new(max: usize) -> Self {
         Self {
-            prime_sieve: prime_sieve(max),
+            prime_sieve: PrimeSieve::new(max),
             current_index: 1,
         }
     }
@@ -25,10 +25,10 @@ impl CircularPrimeIterator {
     fn is_circular_prime(&self, prime: usize) -> bool {
         let mut digits = prime.as_decimal().rotate_left();
         while digits.number() != prime {
-            if !self.prime_sieve[digits.number()] { return false; }
+            if !self.prime_sieve.is_prime(digits.number()) { return false; }
             digits = digits.rotate_left();
         }
-        self.prime_sieve[prime]
+        self.prime_sieve.is_prime(prime)
     }
 }
 
@@ -37,10 +37,10 @@ impl Iterator for CircularPrimeIterator {
 
     fn next(&mut self) -> Option<Self::Item> {
         self.current_index += 1;
-        while self.current_index < self.prime_sieve.len() && !self.is_circular_prime(self.current_index) {
+        while self.current_index <= self.prime_sieve.max() && !self.is_circular_prime(self.current_index) {
             self.current_index += 1;
         }
-        if self.current_index == self.prime_sieve.len() {
+        if self.current_index > self.prime_sieve.max() {
             None
         } else {
             Some(self.current_index)
@@ -1,5 +1,5 @@
 use integers::Integer;
-use number_lists::{prime_iterator, PrimeIterator};
+use number_lists::{prime_iterator, Primality, PrimeIterator};
 
 pub fn p0037_solver() -> String {
     truncatable_primes().sum::<u64>().to_string()
@@ -28,11 +28,11 @@ impl Iterator for TruncatablePrimeIterator {
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.count == 11 { return None }
-        while let Some(p) = self.primes.next() {
+        for p in self.primes.by_ref() {
             if p < 10 { continue; }
             let pd = p.as_decimal();
-            if pd.truncate_left().all(|p2| self.primes.is_prime(p2.number())) &&
-                pd.truncate_right().all(|p2| self.primes.is_prime(p2.number())) {
+            if pd.truncate_left().all(|p2| p2.number().is_prime()) &&
+                pd.truncate_right().all(|p2| p2.number().is_prime()) {
                 self.count += 1;
                 return Some(p)
             }
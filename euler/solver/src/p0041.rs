@@ -1,5 +1,5 @@
 use integers::Integer;
-use number_lists::prime_sieve;
+use number_lists::PrimeSieve;
 
 pub fn p0041_solver() -> String {
     // 2, 3, 5, 6, 8, 9 pandigital numbers are all divisible by 3 by sum of digits
@@ -12,12 +12,12 @@ pub fn p0041_solver() -> String {
 }
 
 fn pandigital_primes(n: u32) -> Vec<usize> {
-    let primes = prime_sieve(10usize.pow(n));
+    let primes = PrimeSieve::new(10usize.pow(n));
     let mut digits = 1usize.as_decimal();
     (2..=n as usize).for_each(|d| digits.concatenate(d.as_decimal()));
     digits.permutations().iter()
         .map(|p| p.number())
-        .filter(|&p| primes[p])
+        .filter(|&p| primes.is_prime(p))
         .collect()
 }
 
@@ -1,25 +1,33 @@
-use integers::digits::Digits;
-use integers::Integer;
+use algorithms::combinatorics::next_permutation;
 
 pub fn p0043_solver() -> String {
     sub_string_divisibility().sum::<u64>().to_string()
 }
 
 fn sub_string_divisibility() -> impl Iterator<Item=u64> {
-    1234567890u64.as_decimal()
-        .permutations()
-        .into_iter()
+    let mut next = Some([0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    std::iter::from_fn(move || {
+        let current = next?;
+        let mut following = current;
+        next = next_permutation(&mut following).then_some(following);
+        Some(current)
+    })
         .filter(is_sub_string_divisible)
-        .map(|d| d.number())
+        .map(|digits| number(&digits))
 }
 
-fn is_sub_string_divisible(digits: &Digits<u64>) -> bool {
-    digits.get(0).unwrap() != 0 &&
-        digits.range(1..=3).number() % 2 == 0 &&
-        digits.range(2..=4).number() % 3 == 0 &&
-        digits.range(3..=5).number() % 5 == 0 &&
-        digits.range(4..=6).number() % 7 == 0 &&
-        digits.range(5..=7).number() % 11 == 0 &&
-        digits.range(6..=8).number() % 13 == 0 &&
-        digits.range(7..=9).number() % 17 == 0
+fn is_sub_string_divisible(digits: &[u64; 10]) -> bool {
+    digits[0] != 0 &&
+        [2, 3, 5, 7, 11, 13, 17].iter()
+            .enumerate()
+            .all(|(i, p)| number(&digits[i + 1..=i + 3]).is_multiple_of(*p))
+}
+
+fn number(digits: &[u64]) -> u64 {
+    digits.iter().fold(0, |n, d| 10 * n + d)
+}
+
+#[test]
+fn finds_sub_string_divisible_pandigitals() {
+    assert!(sub_string_divisibility().any(|n| n == 1406357289));
 }
@@ -1,4 +1,4 @@
-use integers::Integer;
+use integers::factorization::SmallestPrimeFactors;
 
 pub fn p0047_solver() -> String {
     distinct_prime_factors(4).next().unwrap().to_string()
@@ -11,6 +11,7 @@ fn distinct_prime_factors(size: usize) -> impl Iterator<Item=usize> {
 struct DistinctPrimeFactorIterator {
     size: usize,
     numbers: Vec<usize>,
+    factors: SmallestPrimeFactors,
 }
 
 impl DistinctPrimeFactorIterator {
@@ -18,11 +19,19 @@ impl DistinctPrimeFactorIterator {
         Self {
             size,
             numbers: vec!(1),
+            factors: SmallestPrimeFactors::new(1000),
         }
     }
 
-    fn find_next(&self, last: usize) -> usize {
-        (last + 1..).find(|n| n.factorize().distinct_count() == self.size).unwrap()
+    fn distinct_count(&mut self, n: usize) -> usize {
+        if n > self.factors.max() {
+            self.factors = SmallestPrimeFactors::new(2 * n);
+        }
+        self.factors.factorize(n).distinct_count()
+    }
+
+    fn find_next(&mut self, last: usize) -> usize {
+        (last + 1..).find(|&n| self.distinct_count(n) == self.size).unwrap()
     }
 
     fn fill(&mut self) {
@@ -54,4 +63,4 @@ impl Iterator for DistinctPrimeFactorIterator {
 fn finds_consecutive_numbers_with_distinct_prime_factors() {
     assert_eq!(distinct_prime_factors(2).next().unwrap(), 14);
     assert_eq!(distinct_prime_factors(3).next().unwrap(), 644);
-}
\ No newline at end of file
+}
@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 use itertools::Itertools;
 use integers::Integer;
-use number_lists::prime_sieve;
+use number_lists::PrimeSieve;
 
 pub fn p0049_solver() -> String {
     prime_permutations()
@@ -11,16 +11,16 @@ pub fn p0049_solver() -> String {
 }
 
 fn prime_permutations() -> impl Iterator<Item=(usize, usize, usize)> {
-    let primes = prime_sieve(10_000);
+    let primes = PrimeSieve::new(10_000);
     let mut v: HashSet<(usize, usize, usize)> = HashSet::new();
     for i in 1000..10_000 {
-        if !primes[i] { continue; }
+        if !primes.is_prime(i) { continue; }
         let all_primes: Vec<usize> = i.as_decimal()
             .permutations()
             .into_iter()
             .map(|n| n.number())
             .filter(|&c| (1000..10000).contains(&c))
-            .filter(|&c| primes[c])
+            .filter(|&c| primes.is_prime(c))
             .sorted()
             .collect();
         if all_primes.len() < 3 { continue; }
@@ -1,65 +1,33 @@
-use std::collections::VecDeque;
-use itertools::Itertools;
-
-use number_lists::prime_iterator;
+use number_lists::PrimeSieve;
 
 pub fn p0050_solver() -> String {
-    // todo slow solution
-    consecutive_prime_sum(1_000_000)
-        .sorted_by_key(|(_, v)| 1_000_000 - v.len())
-        .next()
-        .unwrap()
-        .0
-        .to_string()
-}
-
-fn consecutive_prime_sum(max: usize) -> impl Iterator<Item=(usize, Vec<usize>)> {
-    ConsecutivePrimeSumIterator::new(max)
-}
-
-struct ConsecutivePrimeSumIterator {
-    primes: Vec<usize>,
-    current_index: usize,
+    longest_consecutive_prime_sum(1_000_000).0.to_string()
 }
 
-impl ConsecutivePrimeSumIterator {
-    fn new(max: usize) -> Self {
-        Self { primes: prime_iterator().take_while(|&p| p < max).collect(), current_index: 0 }
+/// Prime below `max` written as the sum of the most consecutive primes, with the number of
+/// terms. Runs are tried from the longest down, so the first prime sum found wins.
+fn longest_consecutive_prime_sum(max: usize) -> (usize, usize) {
+    let sieve = PrimeSieve::new(max - 1);
+    let mut prefix_sums = vec!(0);
+    for p in sieve.primes() {
+        let sum = prefix_sums.last().unwrap() + p;
+        if sum >= max { break; }
+        prefix_sums.push(sum);
     }
-}
-
-impl Iterator for ConsecutivePrimeSumIterator {
-    type Item = (usize, Vec<usize>);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        while self.current_index < self.primes.len() - 1 {
-            self.current_index += 1;
-            let current = self.primes[self.current_index];
-            let mut sum = 0;
-            let mut summed_primes = VecDeque::new();
-            let mut hi = 0;
-            while sum != current {
-                while sum < current {
-                    sum += self.primes[hi];
-                    summed_primes.push_back(self.primes[hi]);
-                    hi += 1;
-                }
-                while sum > current {
-                    sum -= summed_primes.pop_front().unwrap();
-                }
-            }
-            if summed_primes.len() > 1 {
-                return Some((current, summed_primes.into_iter().collect()));
-            }
+    let primes: Vec<usize> = sieve.primes().collect();
+    for terms in (2..prefix_sums.len()).rev() {
+        let mut sum = prefix_sums[terms];
+        for start in 0.. {
+            if sum >= max { break; }
+            if sieve.is_prime(sum) { return (sum, terms) }
+            sum += primes[start + terms] - primes[start];
         }
-        None
     }
+    panic!("no prime below {max} is a sum of consecutive primes")
 }
 
 #[test]
 fn finds_primes_which_are_sums_of_consecutive_primes() {
-    let prime_41 = consecutive_prime_sum(100).find(|&(n, _)| n == 41).unwrap().1;
-    assert_eq!(prime_41, vec!(2, 3, 5, 7, 11, 13));
-    let prime_953 = consecutive_prime_sum(1000).find(|&(n, _)| n == 953).unwrap().1;
-    assert_eq!(prime_953.len(), 21);
-}
\ No newline at end of file
+    assert_eq!(longest_consecutive_prime_sum(100), (41, 6));
+    assert_eq!(longest_consecutive_prime_sum(1000), (953, 21));
+}
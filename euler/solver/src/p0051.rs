@@ -1,7 +1,7 @@
 use itertools::Itertools;
 
 use integers::digits::Digits;
-use number_lists::prime_sieve;
+use number_lists::PrimeSieve;
 
 pub fn p0051_solver() -> String {
     // todo slow solution
@@ -18,21 +18,21 @@ fn prime_digit_replacements() -> impl Iterator<Item=Vec<usize>> {
 }
 
 struct PrimeDigitReplacementsIterator {
-    primes: Vec<bool>,
+    primes: PrimeSieve,
     current: usize,
 }
 
 impl PrimeDigitReplacementsIterator {
     fn new() -> Self {
         Self {
-            primes: prime_sieve(1000),
+            primes: PrimeSieve::new(1000),
             current: 0,
         }
     }
     
     fn update_primes(&mut self) {
-        if self.current * 100 >= self.primes.len() {
-            self.primes = prime_sieve(self.primes.len() * 100);
+        if self.current * 100 > self.primes.max() {
+            self.primes = PrimeSieve::new((self.primes.max() + 1) * 100);
         }
     }
 }
@@ -57,7 +57,7 @@ impl Iterator for PrimeDigitReplacementsIterator {
                 d2.set(j, i);
             }
             let n = d2.number_in_base(10);
-            if self.primes[n] {
+            if self.primes.is_prime(n) {
                 v.push(n);
             }
         }
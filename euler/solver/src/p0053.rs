@@ -1,4 +1,4 @@
-use num_integer::binomial;
+use algorithms::combinatorics::binomial;
 
 pub fn p0053_solver() -> String {
     combinatoric_selections()
@@ -7,9 +7,9 @@ pub fn p0053_solver() -> String {
         .to_string()
 }
 
-fn combinatoric_selections() -> impl Iterator<Item=(u128,u128,u128)> {
+fn combinatoric_selections() -> impl Iterator<Item=(u64,u64,u128)> {
     (1..=100)
-        .flat_map(|n| (1..=n).map(move |k| (n, k, binomial(n, k))))
+        .flat_map(|n| (1..=n).map(move |k| (n, k, binomial(n, k).unwrap())))
 }
 
 #[test]
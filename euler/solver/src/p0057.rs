@@ -0,0 +1,19 @@
+use number_theory::ContinuedFractions;
+
+pub fn p0057_solver() -> String {
+    square_root_convergents(1000).to_string()
+}
+
+fn square_root_convergents(expansions: usize) -> usize {
+    2u32.sqrt_continued_fraction()
+        .convergents()
+        .skip(1)
+        .take(expansions)
+        .filter(|(n, d)| n.as_decimal().len() > d.as_decimal().len())
+        .count()
+}
+
+#[test]
+fn counts_expansions_with_a_longer_numerator() {
+    assert_eq!(square_root_convergents(8), 1);
+}
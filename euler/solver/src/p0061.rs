@@ -0,0 +1,40 @@
+use number_lists::{Figurate, polygonals};
+
+pub fn p0061_solver() -> String {
+    cyclical_figurate_numbers(&[3, 4, 5, 6, 7, 8]).unwrap().iter().sum::<u32>().to_string()
+}
+
+/// Four-digit numbers, each of a different polygonal order, where the last two digits of each
+/// number are the first two of the next one, the last wrapping around to the first.
+fn cyclical_figurate_numbers(sides: &[u32]) -> Option<Vec<u32>> {
+    let (&first, rest) = sides.split_last()?;
+    polygonals(first)
+        .in_range(1000..10000)
+        .find_map(|start| extend_cycle(vec!(start), rest))
+}
+
+fn extend_cycle(cycle: Vec<u32>, sides: &[u32]) -> Option<Vec<u32>> {
+    let last = *cycle.last().unwrap();
+    if sides.is_empty() { return (last % 100 == cycle[0] / 100).then_some(cycle) }
+    (10..100)
+        .map(|suffix| last % 100 * 100 + suffix)
+        .filter(|&next| next >= 1000)
+        .find_map(|next| {
+            sides.iter()
+                .enumerate()
+                .filter(|&(_, &s)| next.is_polygonal(s))
+                .find_map(|(i, _)| {
+                    let mut cycle = cycle.clone();
+                    cycle.push(next);
+                    let sides = [&sides[..i], &sides[i + 1..]].concat();
+                    extend_cycle(cycle, &sides)
+                })
+        })
+}
+
+#[test]
+fn finds_cyclical_figurate_numbers() {
+    let mut cycle = cyclical_figurate_numbers(&[3, 4, 5]).unwrap();
+    cycle.sort();
+    assert_eq!(cycle, vec!(2882, 8128, 8281));
+}